@@ -1,16 +1,219 @@
+use std::num::NonZeroUsize;
+
 use clap::{Parser, ValueEnum};
+use pokesearch::glyphs::Glyphs;
+use serde::Deserialize;
 
 #[non_exhaustive]
-#[derive(Clone, Debug, PartialEq, Eq, Parser)]
+#[derive(Clone, Debug, PartialEq, Parser)]
 #[command(about, author, version, long_about = None)]
 pub struct Arguments {
     /// The search type.
     pub kind: SearchKind,
     /// The search text.
+    #[arg(default_value = "", hide_default_value = true)]
     pub text: Box<str>,
+    /// Whether to search using the given text as-is, rather than normalizing it into an API name.
+    #[arg(long = "raw-name")]
+    pub raw_name: bool,
+    /// A range of national Pokédex numbers to search for, such as `1-9`.
+    #[arg(long = "range")]
+    pub range: Option<Box<str>>,
+    /// Whether to allow ranges larger than the usual limit.
+    #[arg(long = "force", requires = "range")]
+    pub force: bool,
     /// The cache directory.
     #[arg(long = "cache-dir", default_value = ".cache")]
     pub cache_dir: Box<str>,
+    /// The configuration file, defaulting to one within the user's configuration directory.
+    #[arg(long = "config")]
+    pub config: Option<Box<str>>,
+    /// The maximum number of requests to issue at the same time.
+    #[arg(long = "concurrency", default_value = "8")]
+    pub concurrency: NonZeroUsize,
+    /// Whether to skip confirmation prompts.
+    #[arg(short = 'y', long = "yes")]
+    pub yes: bool,
+    /// Whether to log diagnostic information to the standard error stream.
+    #[arg(short = 'v', long = "verbose")]
+    pub verbose: bool,
+    /// Whether to only display essential information.
+    #[arg(short = 'q', long = "quiet")]
+    pub quiet: bool,
+    /// Whether to fail when data is missing an entry in every preferred language, rather than using another language.
+    #[arg(long = "strict")]
+    pub strict: bool,
+    /// The languages to look up names and effects in, in order of preference.
+    #[arg(long = "lang-fallback", value_delimiter = ',', default_value = "en")]
+    pub lang_fallback: Vec<Box<str>>,
+    /// Whether to print the API URL of each resolved resource.
+    #[arg(long = "show-url")]
+    pub show_url: bool,
+    /// Whether to display the identifier of each resolved resource alongside its name.
+    #[arg(long = "show-id")]
+    pub show_id: bool,
+    /// Whether to display the damage relations of each of the Pokémon's types.
+    #[arg(long = "relations")]
+    pub relations: bool,
+    /// Whether to display offensive type match-ups.
+    #[arg(long = "offensive")]
+    pub offensive: bool,
+    /// The minimum multiplier to display within type match-ups.
+    #[arg(long = "threshold")]
+    pub threshold: Option<f64>,
+    /// The minimum multiplier to display within type match-ups, alongside any threshold.
+    #[arg(long = "min-multiplier")]
+    pub min_multiplier: Option<f64>,
+    /// The maximum multiplier to display within type match-ups.
+    #[arg(long = "max-multiplier")]
+    pub max_multiplier: Option<f64>,
+    /// Whether to display type match-up multipliers as fractions.
+    #[arg(long = "pretty")]
+    pub pretty: bool,
+    /// Whether to only use ASCII characters when formatting output.
+    #[arg(long = "ascii-only")]
+    pub ascii_only: bool,
+    /// When to style output using terminal colors.
+    #[arg(long = "color", value_enum, default_value = "auto")]
+    pub color: ColorChoice,
+    /// Whether to explain how each type match-up multiplier was derived.
+    #[arg(long = "explain")]
+    pub explain: bool,
+    /// Whether to display type match-ups on a single line.
+    #[arg(long = "compact-matchup")]
+    pub compact_matchup: bool,
+    /// The maximum number of type match-up multipliers to display, keeping those furthest from ×1.
+    #[arg(long = "max-matchups")]
+    pub max_matchups: Option<NonZeroUsize>,
+    /// Whether to display short effect descriptions.
+    #[arg(long = "short")]
+    pub short: bool,
+    /// Whether to omit the generation that a Pokémon, ability, or move was introduced in.
+    #[arg(long = "no-generation")]
+    pub no_generation: bool,
+    /// Whether to omit the effect descriptions of abilities, moves, and items.
+    #[arg(long = "hide-effect")]
+    pub hide_effect: bool,
+    /// The column count to wrap effect and flavor text at.
+    #[arg(long = "width")]
+    pub width: Option<NonZeroUsize>,
+    /// Whether to display the values a move had in previous versions.
+    #[arg(long = "history")]
+    pub history: bool,
+    /// Whether to note battle mechanics that a move's effect text omits.
+    #[arg(long = "battle-notes")]
+    pub battle_notes: bool,
+    /// Whether to display how an ability's effect changed in previous versions.
+    #[arg(long = "show-effect-changes")]
+    pub show_effect_changes: bool,
+    /// Whether to list names in every available language.
+    #[arg(long = "all-languages")]
+    pub all_languages: bool,
+    /// The maximum number of entries to list.
+    #[arg(long = "limit")]
+    pub limit: Option<usize>,
+    /// Whether to only display the number of listed entries.
+    #[arg(long = "count")]
+    pub count: bool,
+    /// The page of entries to list.
+    #[arg(long = "page")]
+    pub page: Option<NonZeroUsize>,
+    /// The number of entries to list per page.
+    #[arg(long = "page-size", default_value = "50")]
+    pub page_size: NonZeroUsize,
+    /// Only list entries containing the given text.
+    #[arg(long = "filter")]
+    pub filter: Option<Box<str>>,
+    /// Whether to list entries in ascending order.
+    #[arg(long = "ascending")]
+    pub ascending: bool,
+    /// Whether to sort listed entries by their national Pokédex number or identifier.
+    #[arg(long = "by-dex")]
+    pub by_dex: bool,
+    /// The order to list entries in.
+    #[arg(long = "sort-by")]
+    pub sort_by: Option<SortBy>,
+    /// Whether to list each Pokémon once rather than once per form.
+    #[arg(long = "dedupe-forms")]
+    pub dedupe_forms: bool,
+    /// Only list moves with at least the given power.
+    #[arg(long = "min-power")]
+    pub min_power: Option<i64>,
+    /// Only list moves with at most the given power.
+    #[arg(long = "max-power")]
+    pub max_power: Option<i64>,
+    /// Whether to keep moves without a power when filtering moves by power.
+    #[arg(long = "include-status")]
+    pub include_status: bool,
+    /// Only list Pokémon of the given type.
+    #[arg(long = "type")]
+    pub type_filter: Option<Box<str>>,
+    /// Whether to group listed abilities and moves by the generation that introduced them.
+    #[arg(long = "group-by-generation")]
+    pub group_by_generation: bool,
+    /// The Pokémon form to search for.
+    #[arg(long = "form")]
+    pub form: Option<Box<str>>,
+    /// Whether to render the Pokémon or item's sprite.
+    #[arg(long = "sprite")]
+    pub sprite: bool,
+    /// Whether to render the shiny variant of the Pokémon's sprite.
+    #[arg(long = "shiny", requires = "sprite")]
+    pub shiny: bool,
+    /// Whether to list the Pokémon's regional Pokédex numbers.
+    #[arg(long = "dex")]
+    pub dex: bool,
+    /// Whether to list the Pokémon's base stats.
+    #[arg(long = "stats")]
+    pub stats: bool,
+    /// Whether to order base stats from highest to lowest.
+    #[arg(long = "sort-stats", requires = "stats")]
+    pub sort_stats: bool,
+    /// The output format.
+    #[arg(long = "format", default_value = "text")]
+    pub format: OutputFormat,
+    /// Whether to display flavor text.
+    #[arg(long = "flavor")]
+    pub flavor: bool,
+    /// Whether to only display a Pokémon's species overview and Pokédex entry, skipping its types and match-ups.
+    #[arg(long = "species-only", conflicts_with = "form")]
+    pub species_only: bool,
+    /// The game version to display flavor text from.
+    #[arg(long = "flavor-version", requires = "flavor")]
+    pub flavor_version: Option<Box<str>>,
+    /// Whether to list the Pokémon's learnable moves.
+    #[arg(long = "moves")]
+    pub moves: bool,
+    /// Whether to list the locations the Pokémon may be encountered in.
+    #[arg(long = "locations")]
+    pub locations: bool,
+    /// The highest level to list level-up moves for.
+    #[arg(long = "up-to-level", requires = "moves")]
+    pub up_to_level: Option<i64>,
+    /// The earliest generation to list moves from, ignored if a version group is given.
+    #[arg(long = "since", requires = "moves")]
+    pub since: Option<Box<str>>,
+    /// The Pokémon to list unique moves for when comparing move sets, rather than listing shared moves.
+    #[arg(long = "unique-to")]
+    pub unique_to: Option<Box<str>>,
+    /// The version group to filter version-dependent data by.
+    #[arg(long = "version-group")]
+    pub version_group: Option<Box<str>>,
+    /// The generation to filter version-dependent data by.
+    #[arg(long = "generation")]
+    pub generation: Option<Box<str>>,
+}
+
+impl Arguments {
+    /// Returns the set of symbols that output should be formatted with.
+    pub const fn glyphs(&self) -> Glyphs {
+        Glyphs::new(self.ascii_only)
+    }
+
+    /// Returns the order that listed entries were requested in, if any.
+    pub fn sort_by(&self) -> Option<SortBy> {
+        self.sort_by.or(self.by_dex.then_some(SortBy::Id))
+    }
 }
 
 #[non_exhaustive]
@@ -21,4 +224,77 @@ pub enum SearchKind {
     Move,
     Item,
     Type,
+    Pokedex,
+    GrowthRate,
+    Characteristic,
+    DamageClass,
+    Generation,
+    Breed,
+    Machine,
+    List,
+    ItemCategory,
+    Top,
+    TeamCoverage,
+    MoveByEffect,
+    NatureChart,
+    Game,
+    PokeathlonStat,
+    ContestType,
+    CacheStats,
+    ClearCache,
+    MoveSetOverlap,
+    Evolution,
+}
+
+impl SearchKind {
+    /// Returns whether this search kind requires search text.
+    pub const fn requires_text(self) -> bool {
+        !matches!(self, Self::NatureChart | Self::CacheStats | Self::ClearCache)
+    }
+
+    /// Returns whether this search kind can write its output in the given format.
+    pub const fn supports(self, format: OutputFormat) -> bool {
+        match format {
+            OutputFormat::Text => true,
+            OutputFormat::Tsv => matches!(self, Self::Pokemon | Self::Type | Self::List),
+            OutputFormat::Csv | OutputFormat::Json | OutputFormat::Jsonl | OutputFormat::Yaml => {
+                matches!(self, Self::Pokemon | Self::Type)
+            }
+            OutputFormat::Markdown | OutputFormat::Html => {
+                matches!(self, Self::Pokemon | Self::Type | Self::Ability | Self::Move | Self::Item)
+            }
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OutputFormat {
+    Text,
+    Csv,
+    Tsv,
+    Json,
+    Jsonl,
+    Markdown,
+    Yaml,
+    Html,
+}
+
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ColorChoice {
+    /// Style output unless `NO_COLOR` is set, or it is not written to a terminal and `CLICOLOR_FORCE` is unset.
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum SortBy {
+    Name,
+    Id,
 }
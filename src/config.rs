@@ -0,0 +1,113 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::ArgMatches;
+use clap::parser::ValueSource;
+use serde::Deserialize;
+
+use crate::arguments::{Arguments, ColorChoice, OutputFormat};
+
+/// The name of the configuration file.
+pub const FILE_NAME: &str = "pokesearch.toml";
+
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct Config {
+    /// The default output format.
+    pub format: Option<OutputFormat>,
+    /// The default cache directory.
+    pub cache_dir: Option<Box<str>>,
+    /// The default maximum number of entries to list.
+    pub limit: Option<usize>,
+    /// The default languages to look up names and effects in, in order of preference.
+    pub language: Option<Vec<Box<str>>>,
+    /// The default choice of when to style output using terminal colors.
+    pub color: Option<ColorChoice>,
+}
+
+impl Config {
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|v| v.join(env!("CARGO_PKG_NAME")).join(FILE_NAME))
+    }
+
+    pub fn read(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path).with_context(|| format!("failed to read '{}'", path.display()))?;
+
+        toml::from_str(&text).with_context(|| format!("failed to parse '{}'", path.display()))
+    }
+
+    pub fn load(arguments: &Arguments) -> Result<Self> {
+        if let Some(path) = arguments.config.as_deref() {
+            return Self::read(Path::new(path));
+        }
+
+        match Self::default_path() {
+            Some(path) if path.is_file() => Self::read(&path),
+            _ => Ok(Self::default()),
+        }
+    }
+
+    pub fn apply(self, arguments: &mut Arguments, matches: &ArgMatches) {
+        let is_default = |id: &str| matches.value_source(id).is_none_or(|v| v == ValueSource::DefaultValue);
+
+        if let Some(format) = self.format.filter(|_| is_default("format")) {
+            arguments.format = format;
+        }
+        if let Some(cache_dir) = self.cache_dir.filter(|_| is_default("cache_dir")) {
+            arguments.cache_dir = cache_dir;
+        }
+        if let Some(language) = self.language.filter(|_| is_default("lang_fallback")) {
+            arguments.lang_fallback = language;
+        }
+        if let Some(color) = self.color.filter(|_| is_default("color")) {
+            arguments.color = color;
+        }
+        if arguments.limit.is_none() {
+            arguments.limit = self.limit;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::{CommandFactory, FromArgMatches};
+
+    use super::Config;
+    use crate::arguments::{Arguments, ColorChoice};
+
+    fn apply(config: &str, arguments: &[&str]) -> Arguments {
+        let config: Config = toml::from_str(config).unwrap();
+        let matches = Arguments::command().try_get_matches_from(arguments).unwrap();
+        let mut arguments = Arguments::from_arg_matches(&matches).unwrap();
+
+        config.apply(&mut arguments, &matches);
+
+        arguments
+    }
+
+    #[test]
+    fn language_and_color_fill_defaults() {
+        let arguments =
+            self::apply("language = [\"ja\", \"en\"]\ncolor = \"never\"", &["pokesearch", "pokemon", "pikachu"]);
+
+        assert_eq!(arguments.lang_fallback, ["ja".into(), "en".into()]);
+        assert_eq!(arguments.color, ColorChoice::Never);
+    }
+
+    #[test]
+    fn command_line_overrides_language_and_color() {
+        let arguments = self::apply("language = [\"ja\"]\ncolor = \"never\"", &[
+            "pokesearch",
+            "pokemon",
+            "pikachu",
+            "--lang-fallback",
+            "fr",
+            "--color",
+            "always",
+        ]);
+
+        assert_eq!(arguments.lang_fallback, ["fr".into()]);
+        assert_eq!(arguments.color, ColorChoice::Always);
+    }
+}
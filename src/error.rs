@@ -0,0 +1,144 @@
+use std::fmt::Display;
+use std::process::ExitCode;
+
+/// The kinds of failure that are reported using distinct exit codes.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    /// A requested resource does not exist.
+    NotFound,
+    /// The API could not be reached.
+    Network,
+    /// The given arguments are malformed.
+    InvalidArguments,
+    /// Any other failure.
+    Other,
+}
+
+impl ErrorKind {
+    /// Classifies the given error, defaulting to [`ErrorKind::Other`].
+    pub fn of(error: &anyhow::Error) -> Self {
+        if let Some(error) = error.downcast_ref::<Error>() {
+            return error.kind();
+        }
+
+        error.chain().find_map(|v| v.downcast_ref::<rustemon::error::Error>()).map_or(Self::Other, Self::of_rustemon)
+    }
+
+    /// Classifies the given API client error.
+    pub fn of_rustemon(error: &rustemon::error::Error) -> Self {
+        use rustemon::error::Error;
+
+        match error {
+            Error::Reqwest(error) => error.status().map_or(Self::Network, |v| Self::of_status(v.as_u16())),
+            Error::ReqwestMiddleware(_) => Self::Network,
+            Error::UrlParse(_) => Self::InvalidArguments,
+            _ => Self::Other,
+        }
+    }
+
+    /// Classifies the given HTTP status code of a failed response.
+    pub const fn of_status(status: u16) -> Self {
+        match status {
+            404 | 410 => Self::NotFound,
+            408 | 429 | 500 ..= 599 => Self::Network,
+            400 ..= 499 => Self::InvalidArguments,
+            _ => Self::Other,
+        }
+    }
+
+    /// Returns the name used to identify this kind in structured output.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::NotFound => "not_found",
+            Self::Network => "network",
+            Self::InvalidArguments => "invalid_arguments",
+            Self::Other => "other",
+        }
+    }
+
+    pub const fn exit_code(self) -> u8 {
+        match self {
+            Self::Other => 1,
+            Self::NotFound => 2,
+            Self::Network => 3,
+            Self::InvalidArguments => 4,
+        }
+    }
+}
+
+impl From<ErrorKind> for ExitCode {
+    fn from(value: ErrorKind) -> Self {
+        Self::from(value.exit_code())
+    }
+}
+
+/// An error message with an explicit [`ErrorKind`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Error {
+    kind: ErrorKind,
+    message: Box<str>,
+    query: Option<(&'static str, Box<str>)>,
+}
+
+impl Error {
+    pub fn new(kind: ErrorKind, message: impl Into<Box<str>>) -> Self {
+        Self { kind, message: message.into(), query: None }
+    }
+
+    /// Records the resource and search text that caused this error.
+    pub fn with_query(mut self, resource: &'static str, text: impl Into<Box<str>>) -> Self {
+        self.query = Some((resource, text.into()));
+        self
+    }
+
+    pub fn not_found(message: impl Into<Box<str>>) -> Self {
+        Self::new(ErrorKind::NotFound, message)
+    }
+
+    pub fn invalid_arguments(message: impl Into<Box<str>>) -> Self {
+        Self::new(ErrorKind::InvalidArguments, message)
+    }
+
+    pub const fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Returns the resource and search text that caused this error, if known.
+    pub fn query(&self) -> Option<(&'static str, &str)> {
+        self.query.as_ref().map(|(resource, text)| (*resource, &**text))
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Marks an error that has already been reported through structured output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Reported;
+
+impl Display for Reported {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("the error has already been reported")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ErrorKind;
+
+    #[test]
+    fn statuses_are_classified() {
+        assert_eq!(ErrorKind::of_status(404), ErrorKind::NotFound);
+        assert_eq!(ErrorKind::of_status(410), ErrorKind::NotFound);
+        assert_eq!(ErrorKind::of_status(429), ErrorKind::Network);
+        assert_eq!(ErrorKind::of_status(503), ErrorKind::Network);
+        assert_eq!(ErrorKind::of_status(400), ErrorKind::InvalidArguments);
+        assert_eq!(ErrorKind::of_status(301), ErrorKind::Other);
+    }
+}
@@ -0,0 +1,190 @@
+use std::borrow::Cow;
+
+use serde_json::Value;
+
+use crate::glyphs::Glyphs;
+
+pub fn format_multiplier(multiplier: f64, glyphs: Glyphs) -> String {
+    match (multiplier * 1000.0).round() as u32 {
+        0 => "immune".to_owned(),
+        125 => format!("{}{}", glyphs.times, glyphs.eighth),
+        250 => format!("{}{}", glyphs.times, glyphs.quarter),
+        500 => format!("{}{}", glyphs.times, glyphs.half),
+        _ => format!("{}{multiplier}", glyphs.times),
+    }
+}
+
+pub fn csv_field(field: &str) -> Cow<'_, str> {
+    if field.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+/// Replaces the tabs and line breaks within a field, which cannot be escaped in tab-separated values.
+pub fn tsv_field(field: &str) -> Cow<'_, str> {
+    if field.contains(['\t', '\n', '\r']) {
+        Cow::Owned(field.replace(['\t', '\n', '\r'], " "))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+/// Formats the given value as a block-style YAML document.
+pub fn to_yaml(value: &Value) -> String {
+    self::yaml_lines(value).into_iter().map(|v| v + "\n").collect()
+}
+
+fn yaml_lines(value: &Value) -> Vec<String> {
+    match value {
+        Value::Array(items) if !items.is_empty() => items
+            .iter()
+            .flat_map(|item| {
+                let lines = self::yaml_lines(item).into_iter().enumerate();
+
+                lines.map(|(index, line)| if index == 0 { format!("- {line}") } else { format!("  {line}") })
+            })
+            .collect(),
+        Value::Object(map) if !map.is_empty() => map
+            .iter()
+            .flat_map(|(key, value)| match value {
+                Value::Array(v) if !v.is_empty() => self::yaml_block(key, value),
+                Value::Object(v) if !v.is_empty() => self::yaml_block(key, value),
+                _ => vec![format!("{}: {}", self::yaml_scalar(key), self::yaml_lines(value).concat())],
+            })
+            .collect(),
+        Value::Array(_) => vec!["[]".to_owned()],
+        Value::Object(_) => vec!["{}".to_owned()],
+        Value::String(text) => vec![self::yaml_scalar(text).into_owned()],
+        _ => vec![value.to_string()],
+    }
+}
+
+fn yaml_block(key: &str, value: &Value) -> Vec<String> {
+    let lines = self::yaml_lines(value).into_iter().map(|v| format!("  {v}"));
+
+    std::iter::once(format!("{}:", self::yaml_scalar(key))).chain(lines).collect()
+}
+
+fn yaml_scalar(text: &str) -> Cow<'_, str> {
+    const RESERVED: &[&str] = &["true", "false", "null", "yes", "no", "on", "off", "y", "n", "~"];
+
+    let is_plain = text.starts_with(|c: char| c.is_alphabetic())
+        && text.chars().all(|c| c.is_alphanumeric() || matches!(c, ' ' | '-' | '_' | '.'))
+        && text.trim_end() == text
+        && !RESERVED.contains(&&*text.to_lowercase());
+
+    // JSON strings are valid double-quoted YAML scalars.
+    if is_plain { Cow::Borrowed(text) } else { Cow::Owned(Value::from(text).to_string()) }
+}
+
+pub fn html_escape(text: &str) -> Cow<'_, str> {
+    if !text.contains(['&', '<', '>', '"', '\'']) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut escaped = String::with_capacity(text.len());
+
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            character => escaped.push(character),
+        }
+    }
+
+    Cow::Owned(escaped)
+}
+
+/// Returns the color conventionally used to display the type with the given name.
+pub fn type_color(name: &str) -> Option<&'static str> {
+    Some(match name.to_lowercase().as_str() {
+        "normal" => "#a8a77a",
+        "fire" => "#ee8130",
+        "water" => "#6390f0",
+        "electric" => "#f7d02c",
+        "grass" => "#7ac74c",
+        "ice" => "#96d9d6",
+        "fighting" => "#c22e28",
+        "poison" => "#a33ea1",
+        "ground" => "#e2bf65",
+        "flying" => "#a98ff3",
+        "psychic" => "#f95587",
+        "bug" => "#a6b91a",
+        "rock" => "#b6a136",
+        "ghost" => "#735797",
+        "dragon" => "#6f35fc",
+        "dark" => "#705746",
+        "steel" => "#b7b7ce",
+        "fairy" => "#d685ad",
+        "stellar" => "#40b5a5",
+        _ => return None,
+    })
+}
+
+/// Formats a type name as an HTML badge colored using [`type_color`].
+pub fn type_badge(name: &str) -> String {
+    let color = self::type_color(name).unwrap_or("#68a090");
+
+    format!(
+        "<span style=\"background-color: {color}; color: #fff; border-radius: 4px; padding: 0 4px;\">{}</span>",
+        self::html_escape(name)
+    )
+}
+
+pub fn markdown_cell(field: &str) -> Cow<'_, str> {
+    if field.contains(['|', '\n']) {
+        Cow::Owned(field.replace('|', "\\|").replace('\n', " "))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::glyphs::Glyphs;
+
+    #[test]
+    fn multipliers_format_as_fractions() {
+        let expected = [(0.0, "immune"), (0.25, "×¼"), (0.5, "×½"), (1.0, "×1"), (2.0, "×2"), (4.0, "×4")];
+
+        for (multiplier, text) in expected {
+            assert_eq!(super::format_multiplier(multiplier, Glyphs::UNICODE), text);
+        }
+
+        assert_eq!(super::format_multiplier(0.25, Glyphs::ASCII), "x1/4");
+        assert_eq!(super::format_multiplier(0.125, Glyphs::ASCII), "x1/8");
+    }
+
+    #[test]
+    fn ambiguous_yaml_scalars_are_quoted() {
+        for text in ["yes", "No", "ON", "null", "~", "1e3", "42", "a: b", "line\nbreak", "trailing ", "", "-", ".inf"] {
+            assert_eq!(super::yaml_scalar(text), serde_json::Value::from(text).to_string(), "{text:?}");
+        }
+
+        for text in ["pikachu", "Mr. Mime", "ho-oh", "Flabébé", "nan"] {
+            assert_eq!(super::yaml_scalar(text), text);
+        }
+    }
+
+    #[test]
+    fn nested_values_format_as_yaml_blocks() {
+        let value = serde_json::json!({
+            "error": null,
+            "forms": [],
+            "name": "yes",
+            "stats": [{ "base": 45, "name": "hp" }],
+            "types": ["grass", "poison"],
+        });
+
+        assert_eq!(
+            super::to_yaml(&value),
+            "error: null\nforms: []\nname: \"yes\"\nstats:\n  - base: 45\n    name: hp\ntypes:\n  - grass\n  - \
+             poison\n"
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use std::borrow::Cow;
+
+/// The set of symbols used when formatting output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Glyphs {
+    /// The multiplication sign preceding multipliers.
+    pub times: &'static str,
+    /// The fraction representing one eighth.
+    pub eighth: &'static str,
+    /// The fraction representing one quarter.
+    pub quarter: &'static str,
+    /// The fraction representing one half.
+    pub half: &'static str,
+    /// The character filling the upper half of a sprite cell.
+    pub upper_half: char,
+    /// The character filling the lower half of a sprite cell.
+    pub lower_half: char,
+    /// The character filling both halves of a sprite cell, drawn using the upper half's color.
+    pub full: char,
+    /// The frames of the spinner's animation.
+    pub spinner: &'static [char],
+}
+
+impl Glyphs {
+    /// Symbols that only use ASCII characters.
+    pub const ASCII: Self = Self {
+        times: "x",
+        eighth: "1/8",
+        quarter: "1/4",
+        half: "1/2",
+        upper_half: '\'',
+        lower_half: '.',
+        full: '#',
+        spinner: &['|', '/', '-', '\\'],
+    };
+    /// Symbols that require a terminal with Unicode support.
+    pub const UNICODE: Self = Self {
+        times: "×",
+        eighth: "⅛",
+        quarter: "¼",
+        half: "½",
+        upper_half: '▀',
+        lower_half: '▄',
+        full: '▀',
+        spinner: &['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'],
+    };
+
+    pub const fn new(ascii_only: bool) -> Self {
+        if ascii_only { Self::ASCII } else { Self::UNICODE }
+    }
+
+    /// Replaces the symbols and accented letters within text, such as the "é" of "Pokémon" or the "×" of a
+    /// multiplier, when only ASCII may be used.
+    ///
+    /// Characters without an ASCII equivalent are replaced with a question mark.
+    pub fn fold(self, text: &str) -> Cow<'_, str> {
+        if self != Self::ASCII || text.is_ascii() {
+            return Cow::Borrowed(text);
+        }
+
+        let mut folded = String::with_capacity(text.len());
+
+        for character in text.chars() {
+            match character {
+                _ if character.is_ascii() => folded.push(character),
+                '×' => folded.push_str(Self::ASCII.times),
+                '⅛' => folded.push_str(Self::ASCII.eighth),
+                '¼' => folded.push_str(Self::ASCII.quarter),
+                '½' => folded.push_str(Self::ASCII.half),
+                '→' => folded.push_str("->"),
+                '←' => folded.push_str("<-"),
+                '…' => folded.push_str("..."),
+                '–' | '—' | '−' => folded.push('-'),
+                '‘' | '’' => folded.push('\''),
+                '“' | '”' => folded.push('"'),
+                '·' | '•' => folded.push('*'),
+                'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => folded.push('a'),
+                'À' | 'Á' | 'Â' | 'Ã' | 'Ä' | 'Å' => folded.push('A'),
+                'ç' => folded.push('c'),
+                'Ç' => folded.push('C'),
+                'è' | 'é' | 'ê' | 'ë' => folded.push('e'),
+                'È' | 'É' | 'Ê' | 'Ë' => folded.push('E'),
+                'ì' | 'í' | 'î' | 'ï' => folded.push('i'),
+                'Ì' | 'Í' | 'Î' | 'Ï' => folded.push('I'),
+                'ñ' => folded.push('n'),
+                'Ñ' => folded.push('N'),
+                'ò' | 'ó' | 'ô' | 'õ' | 'ö' => folded.push('o'),
+                'Ò' | 'Ó' | 'Ô' | 'Õ' | 'Ö' => folded.push('O'),
+                'ù' | 'ú' | 'û' | 'ü' => folded.push('u'),
+                'Ù' | 'Ú' | 'Û' | 'Ü' => folded.push('U'),
+                'ß' => folded.push_str("ss"),
+                '♀' => folded.push_str("(F)"),
+                '♂' => folded.push_str("(M)"),
+                _ => folded.push('?'),
+            }
+        }
+
+        Cow::Owned(folded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Glyphs;
+
+    #[test]
+    fn labels_fold_to_ascii() {
+        assert_eq!(Glyphs::ASCII.fold("Pokédex Numbers:"), "Pokedex Numbers:");
+        assert_eq!(Glyphs::ASCII.fold("POKÉMON"), "POKEMON");
+        assert_eq!(Glyphs::UNICODE.fold("Pokémon by Speed"), "Pokémon by Speed");
+        assert_eq!(Glyphs::ASCII.fold("×½ → ×¼ → ×⅛"), "x1/2 -> x1/4 -> x1/8");
+        assert_eq!(Glyphs::ASCII.fold("Nidoran♀ – Salamèche"), "Nidoran(F) - Salameche");
+        assert_eq!(Glyphs::ASCII.fold("ポケモン"), "????");
+    }
+}
@@ -0,0 +1,88 @@
+use anyhow::{Result, bail};
+
+/// Returns the first entry matching the predicate, falling back to the first entry.
+#[inline]
+pub fn linear_search<T>(list: &[T], predicate: impl Fn(&&T) -> bool) -> Result<&T> {
+    match list.iter().find(predicate).or_else(|| list.first()) {
+        Some(value) => Ok(value),
+        None => bail!("unable to find a suitable value"),
+    }
+}
+
+/// Returns the first entry matching the predicate, failing if there is none.
+#[inline]
+pub fn linear_search_strict<T>(list: &[T], predicate: impl Fn(&&T) -> bool) -> Result<&T> {
+    match list.iter().find(predicate) {
+        Some(value) => Ok(value),
+        None => bail!("unable to find a matching value"),
+    }
+}
+
+/// An ordered list of languages to look up localized entries in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LanguagePrefs {
+    languages: Vec<Box<str>>,
+    strict: bool,
+}
+
+impl LanguagePrefs {
+    /// Creates a new list of preferences, which fails searches rather than falling back to the first entry if strict.
+    pub fn new(languages: impl IntoIterator<Item = impl Into<Box<str>>>, strict: bool) -> Self {
+        Self { languages: languages.into_iter().map(Into::into).collect(), strict }
+    }
+
+    /// Returns the entry in the most preferred language available.
+    pub fn search<'l, T>(&self, list: &'l [T], language: impl Fn(&T) -> &str) -> Result<&'l T> {
+        let entry =
+            self.languages.iter().find_map(|name| self::linear_search_strict(list, |v| language(v) == &**name).ok());
+
+        match entry {
+            Some(entry) => Ok(entry),
+            None if self.strict => {
+                bail!("missing an entry in any of the preferred languages: {}", self.languages.join(", "))
+            }
+            // English is the API's most complete language, so it is tried before any other entry.
+            None => self::linear_search(list, |v| language(v) == "en"),
+        }
+    }
+}
+
+impl Default for LanguagePrefs {
+    fn default() -> Self {
+        Self::new(["en"], false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LanguagePrefs;
+
+    #[test]
+    fn linear_search_falls_back_to_the_first_entry() {
+        let list = [1, 2, 3];
+
+        assert_eq!(super::linear_search(&list, |v| **v == 2).unwrap(), &2);
+        assert_eq!(super::linear_search(&list, |v| **v == 4).unwrap(), &1);
+        assert!(super::linear_search(&[] as &[i32], |_| true).is_err());
+    }
+
+    #[test]
+    fn linear_search_strict_fails_without_a_match() {
+        let list = [1, 2, 3];
+
+        assert_eq!(super::linear_search_strict(&list, |v| **v == 2).unwrap(), &2);
+        assert!(super::linear_search_strict(&list, |v| **v == 4).is_err());
+        assert!(super::linear_search_strict(&[] as &[i32], |_| true).is_err());
+    }
+
+    #[test]
+    fn language_preferences_fall_back_in_order() {
+        let list = ["fr", "en", "ja"];
+        let search = |prefs: LanguagePrefs| prefs.search(&list, |v| v).ok().copied();
+
+        assert_eq!(search(LanguagePrefs::new(["de", "ja"], false)), Some("ja"));
+        assert_eq!(search(LanguagePrefs::new(["de"], false)), Some("en"));
+        assert_eq!(search(LanguagePrefs::new(["de"], true)), None);
+        assert_eq!(LanguagePrefs::new(["de"], false).search(&["fr", "ja"], |v| v).ok(), Some(&"fr"));
+    }
+}
@@ -0,0 +1,16 @@
+//! Looks up Pokémon and their type match-ups from PokéAPI, returning serializable results.
+//!
+//! [`pokemon_matchup`] and [`type_matchup`] perform a whole lookup, while [`TypeMatchup`] may be built from types that
+//! have already been requested and rendered in any of the formats that the `pokesearch` binary supports. Localized
+//! names are chosen using the given [`LanguagePrefs`] rather than any process-wide setting.
+
+pub mod format;
+pub mod glyphs;
+pub mod language;
+pub mod lookup;
+pub mod matchup;
+
+pub use glyphs::Glyphs;
+pub use language::LanguagePrefs;
+pub use lookup::{PokemonMatchup, pokemon_matchup, type_matchup};
+pub use matchup::{Direction, Multiplier, TypeMatchup};
@@ -0,0 +1,94 @@
+use anyhow::Result;
+use rustemon::Follow;
+use rustemon::client::RustemonClient;
+use serde::Serialize;
+
+use crate::language::LanguagePrefs;
+use crate::matchup::{Direction, Multiplier, TypeMatchup};
+
+/// A Pokémon's types, alongside the damage multipliers of every type against it or from it.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct PokemonMatchup {
+    /// The Pokémon's identifier.
+    pub id: i64,
+    /// The name of the Pokémon's species, in the most preferred language available.
+    pub name: String,
+    /// The names of the Pokémon's types, ordered by slot.
+    pub types: Vec<String>,
+    /// The damage multipliers, from the highest to the lowest.
+    pub multipliers: Vec<Multiplier>,
+}
+
+/// Returns the identifier at the end of the given resource URL.
+#[inline]
+pub fn resource_id(url: &str) -> Option<i64> {
+    url.trim_end_matches('/').rsplit('/').next()?.parse().ok()
+}
+
+/// Looks up the Pokémon with the given API name and applies each of its types in the given direction.
+pub async fn pokemon_matchup(
+    client: &RustemonClient,
+    name: &str,
+    direction: Direction,
+    prefs: &LanguagePrefs,
+) -> Result<PokemonMatchup> {
+    let mut pokemon = rustemon::pokemon::pokemon::get_by_name(name, client).await?;
+    let species = pokemon.species.follow(client).await?;
+    let mut types = Vec::with_capacity(pokemon.types.len());
+
+    pokemon.types.sort_unstable_by_key(|v| v.slot);
+
+    for type_ in &pokemon.types {
+        types.push(type_.type_.follow(client).await?);
+    }
+
+    let mut matchup = TypeMatchup::new(client, prefs.clone()).await?;
+
+    matchup.apply_pokemon_types(&types, direction)?;
+
+    let mut type_names = Vec::with_capacity(types.len());
+
+    for type_ in &types {
+        type_names.push(prefs.search(&type_.names, |v| &v.language.name)?.name.clone());
+    }
+
+    Ok(PokemonMatchup {
+        id: pokemon.id,
+        name: prefs.search(&species.names, |v| &v.language.name)?.name.clone(),
+        types: type_names,
+        multipliers: matchup.multipliers(),
+    })
+}
+
+/// Looks up the types with the given API names and combines their relations in the given direction.
+///
+/// Offensive match-ups keep the best multiplier of any type, as each attack only uses one of them.
+pub async fn type_matchup(
+    client: &RustemonClient,
+    names: &[&str],
+    direction: Direction,
+    prefs: &LanguagePrefs,
+) -> Result<Vec<Multiplier>> {
+    let mut matchup = TypeMatchup::new(client, prefs.clone()).await?;
+
+    for name in names {
+        let type_ = rustemon::pokemon::type_::get_by_name(name, client).await?;
+
+        match direction {
+            Direction::Offensive => matchup.apply_offensive_relations(&type_, false)?,
+            Direction::Defensive => matchup.apply_type(&type_, Direction::Defensive)?,
+        }
+    }
+
+    Ok(matchup.multipliers())
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn resource_ids_are_read_from_urls() {
+        assert_eq!(super::resource_id("https://pokeapi.co/api/v2/type/10/"), Some(10));
+        assert_eq!(super::resource_id("https://pokeapi.co/api/v2/type/10"), Some(10));
+        assert_eq!(super::resource_id("https://pokeapi.co/api/v2/type/fire/"), None);
+    }
+}
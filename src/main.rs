@@ -1,166 +1,2483 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::error::Error;
+use std::fmt::Display;
 use std::future::Future;
+use std::num::NonZeroUsize;
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Instant;
 
-use anyhow::{Result, bail};
-use arguments::{Arguments, SearchKind};
-use clap::Parser;
-use rustemon::Follow;
+use anyhow::{Context, Result, bail};
+use arguments::{Arguments, OutputFormat, SearchKind, SortBy};
+use clap::{ArgMatches, CommandFactory, FromArgMatches};
+use config::Config;
+use error::ErrorKind;
+use pokesearch::{Direction, LanguagePrefs, TypeMatchup, format, language, lookup};
 use rustemon::client::{CACacheManager, RustemonClient, RustemonClientBuilder};
-use utility::{TypeMatchup, english_search, english_search_by};
+use rustemon::model::games::{Generation, Version, VersionGroup};
+use rustemon::model::moves::{Move, PastMoveStatValues};
+use rustemon::model::pokemon::{
+    AbilityEffectChange, Characteristic, Pokemon, PokemonForm, PokemonMove, PokemonSpecies, PokemonType, Stat,
+};
+use rustemon::model::resource::{FlavorText, Name, NamedApiResource};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use utility::{Follow, LearnMethod, Resource, localized_search, localized_search_by};
 
 mod arguments;
+mod config;
+mod error;
+mod spinner;
+mod sprite;
 mod utility;
 
-fn main() -> Result<()> {
-    let arguments = Arguments::parse();
-    let manager = CACacheManager { path: (&*arguments.cache_dir).into() };
-    let client = RustemonClientBuilder::default().with_manager(manager).try_build()?;
+fn main() -> ExitCode {
+    match self::run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            if error.downcast_ref::<error::Reported>().is_none() {
+                eprintln!("Error: {error:?}");
+            }
+
+            ErrorKind::of(&error).into()
+        }
+    }
+}
+
+fn run() -> Result<()> {
+    let matches = Arguments::command().try_get_matches().unwrap_or_else(|error| self::exit_with(&error));
+    let mut arguments = Arguments::from_arg_matches(&matches).unwrap_or_else(|error| self::exit_with(&error));
     let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
 
-    runtime.block_on(self::async_main(&arguments, client))
+    let result = self::setup(&mut arguments, &matches).and_then(|()| {
+        let client = self::client(&arguments)?;
+
+        runtime.block_on(self::async_main(&arguments, client))
+    });
+
+    runtime.block_on(self::report(&arguments, result))
+}
+
+fn setup(arguments: &mut Arguments, matches: &ArgMatches) -> Result<()> {
+    Config::load(arguments)?.apply(arguments, matches);
+
+    if arguments.kind.requires_text() && arguments.range.is_none() && arguments.text.trim().is_empty() {
+        bail!(error::Error::invalid_arguments(format!("expected search text for the {:?} search", arguments.kind)));
+    }
+
+    // Species overviews are only written as text, unlike the rest of a Pokémon search.
+    if !arguments.kind.supports(arguments.format) || (arguments.species_only && arguments.format != OutputFormat::Text)
+    {
+        bail!(error::Error::invalid_arguments(format!(
+            "the {:?} output format is not supported by the {:?} search",
+            arguments.format, arguments.kind
+        )));
+    }
+
+    arguments.cache_dir = utility::expand_path(&arguments.cache_dir).into();
+    utility::set_cache_dir(&arguments.cache_dir);
+    utility::set_concurrency(arguments.concurrency.get());
+    utility::set_language_prefs(LanguagePrefs::new(arguments.lang_fallback.iter().cloned(), arguments.strict));
+    utility::set_show_url(arguments.show_url);
+    utility::set_color(arguments.color);
+
+    if arguments.verbose {
+        utility::set_verbose(&arguments.cache_dir);
+    }
+
+    Ok(())
+}
+
+fn client(arguments: &Arguments) -> Result<RustemonClient> {
+    let manager = CACacheManager { path: (&*arguments.cache_dir).into() };
+
+    RustemonClientBuilder::default().with_manager(manager).try_build().map_err(Into::into)
+}
+
+fn exit_with(error: &clap::Error) -> ! {
+    // Help and version requests are reported as errors, but should still exit successfully.
+    if !error.use_stderr() {
+        let _ = error.print();
+
+        std::process::exit(0)
+    }
+
+    let arguments = std::env::args_os().map(|v| v.to_string_lossy().into_owned());
+
+    match self::format_argument(arguments) {
+        Some(format @ (OutputFormat::Json | OutputFormat::Jsonl | OutputFormat::Yaml)) => {
+            let message = error.to_string();
+            // Only the first line is kept, as the rest of clap's message describes the usage for terminals.
+            let message = message.lines().next().unwrap_or_default().trim_start_matches("error: ");
+            let output = self::error_object(ErrorKind::InvalidArguments, message, None, None);
+
+            if let Ok(output) = self::render_error(format, &output) {
+                print!("{output}");
+            }
+        }
+        _ => {
+            let _ = error.print();
+        }
+    }
+
+    std::process::exit(ErrorKind::InvalidArguments.exit_code().into())
+}
+
+/// Returns the output format given on the command line, if it can be read without parsing the other arguments.
+fn format_argument(arguments: impl IntoIterator<Item = String>) -> Option<OutputFormat> {
+    let mut arguments = arguments.into_iter();
+    let mut format = None;
+
+    while let Some(argument) = arguments.next() {
+        if argument == "--format" {
+            format = arguments.next();
+        } else if let Some(value) = argument.strip_prefix("--format=") {
+            format = Some(value.to_owned());
+        }
+    }
+
+    <OutputFormat as clap::ValueEnum>::from_str(&format?, false).ok()
 }
 
 async fn async_main(arguments: &Arguments, client: RustemonClient) -> Result<()> {
-    let api_text = arguments.text.replace(' ', "-").to_lowercase();
+    let api_text =
+        if arguments.raw_name { arguments.text.to_string() } else { arguments.text.replace(' ', "-").to_lowercase() };
+    let timer = Instant::now();
+
+    if arguments.verbose {
+        async_eprintln!("searching {:?} '{}' using cache '{}'", arguments.kind, arguments.text, arguments.cache_dir)
+            .await?;
+    }
+
+    if !arguments.quiet {
+        spinner::start(format!("Resolving {}...", arguments.text), arguments.glyphs().spinner);
+    }
 
-    match arguments.kind {
+    let result = match arguments.kind {
+        SearchKind::Pokemon if arguments.range.is_some() => self::run_pokemon_range(arguments, client).await,
         SearchKind::Pokemon => self::run_pokemon(arguments, client, &api_text).await,
         SearchKind::Ability => self::run_ability(arguments, client, &api_text).await,
         SearchKind::Move => self::run_move(arguments, client, &api_text).await,
         SearchKind::Item => self::run_item(arguments, client, &api_text).await,
         SearchKind::Type => self::run_type(arguments, client, &api_text).await,
+        SearchKind::Pokedex => self::run_pokedex(arguments, client, &api_text).await,
+        SearchKind::GrowthRate => self::run_growth_rate(arguments, client, &api_text).await,
+        SearchKind::Characteristic => self::run_characteristic(arguments, client, &api_text).await,
+        SearchKind::DamageClass => self::run_damage_class(arguments, client, &api_text).await,
+        SearchKind::Generation => self::run_generation(arguments, client, &api_text).await,
+        SearchKind::Breed => self::run_breed(arguments, client, &api_text).await,
+        SearchKind::Machine => self::run_machine(arguments, client, &api_text).await,
+        SearchKind::List => self::run_list(arguments, client, &api_text).await,
+        SearchKind::ItemCategory => self::run_item_category(arguments, client, &api_text).await,
+        SearchKind::Top => self::run_top(arguments, client, &api_text).await,
+        SearchKind::TeamCoverage => self::run_team_coverage(arguments, client, &api_text).await,
+        SearchKind::MoveByEffect => self::run_move_by_effect(arguments, client, &api_text).await,
+        SearchKind::NatureChart => self::run_nature_chart(arguments, client, &api_text).await,
+        SearchKind::Game => self::run_game(arguments, client, &api_text).await,
+        SearchKind::PokeathlonStat => self::run_pokeathlon_stat(arguments, client, &api_text).await,
+        SearchKind::ContestType => self::run_contest_type(arguments, client, &api_text).await,
+        SearchKind::CacheStats => self::run_cache_stats(arguments, client, &api_text).await,
+        SearchKind::ClearCache => self::run_clear_cache(arguments, client, &api_text).await,
+        SearchKind::MoveSetOverlap => self::run_move_set_overlap(arguments, client, &api_text).await,
+        SearchKind::Evolution => self::run_evolution(arguments, client, &api_text).await,
+    };
+
+    spinner::stop();
+
+    if arguments.verbose {
+        async_eprintln!("finished in {:.2?}", timer.elapsed()).await?;
+    }
+
+    result
+}
+
+/// Writes a failure to standard error, or to standard output in the structured output formats, marking it as reported.
+async fn report(arguments: &Arguments, result: Result<()>) -> Result<()> {
+    let Err(error) = result else { return Ok(()) };
+
+    if error.downcast_ref::<error::Reported>().is_none() {
+        self::emit_error(arguments, &error).await?;
+    }
+
+    Err(error).context(error::Reported)
+}
+
+/// Writes a failure to standard error, or to standard output in the structured output formats.
+async fn emit_error(arguments: &Arguments, error: &anyhow::Error) -> Result<()> {
+    match arguments.format {
+        OutputFormat::Json | OutputFormat::Jsonl | OutputFormat::Yaml => {}
+        OutputFormat::Text | OutputFormat::Csv | OutputFormat::Tsv | OutputFormat::Markdown | OutputFormat::Html => {
+            return async_eprintln!("Error: {}", arguments.glyphs().fold(&format!("{error:?}")))
+                .await
+                .map_err(Into::into);
+        }
     }
+
+    let kind = ErrorKind::of(error);
+    let query = error.downcast_ref::<error::Error>().and_then(error::Error::query);
+    let suggestion = match query {
+        Some((resource, text)) if kind == ErrorKind::NotFound => match self::client(arguments) {
+            Ok(client) => self::suggestion(&client, resource, text).await.ok().flatten(),
+            Err(_) => None,
+        },
+        _ => None,
+    };
+    let message = error.to_string();
+    let output = self::error_object(
+        kind,
+        &arguments.glyphs().fold(&message),
+        query.map(|(_, text)| text),
+        suggestion.as_deref(),
+    );
+
+    async_print!("{}", self::render_error(arguments.format, &output)?).await.map_err(Into::into)
+}
+
+/// Returns the structured `error` object describing a failure.
+fn error_object(kind: ErrorKind, message: &str, query: Option<&str>, suggestion: Option<&str>) -> serde_json::Value {
+    serde_json::json!({
+        "error": {
+            "kind": kind.name(),
+            "message": message,
+            "query": query,
+            "suggestion": suggestion,
+        }
+    })
+}
+
+/// Renders a structured `error` object in the given output format, which should be JSON, JSON Lines, or YAML.
+fn render_error(format: OutputFormat, output: &serde_json::Value) -> Result<String> {
+    Ok(match format {
+        OutputFormat::Json => serde_json::to_string_pretty(output)? + "\n",
+        OutputFormat::Yaml => format::to_yaml(output),
+        _ => format!("{output}\n"),
+    })
 }
 
 #[inline]
-async fn search<T, E: Error>(name: &'static str, text: &str, future: impl Future<Output = Result<T, E>>) -> Result<T> {
+async fn search<T: Resource, E: Error + 'static>(
+    name: &'static str,
+    text: &str,
+    slug: impl Display,
+    future: impl Future<Output = Result<T, E>>,
+) -> Result<T> {
+    // Searches request their endpoints without a trailing slash, which is also how they are cached.
+    let url = format!("https://pokeapi.co/api/v2/{}/{slug}", T::ENDPOINT);
+
+    utility::log_request(&url).await?;
+
     match future.await {
-        Ok(value) => Ok(value),
-        Err(error) => bail!("failed to resolve {name} '{text}' - {error}"),
+        Ok(value) => {
+            utility::print_url(&value).await?;
+
+            Ok(value)
+        }
+        Err(error) => {
+            let kind = match (&error as &dyn Error).downcast_ref() {
+                // Decoding errors carry no status, so the request is repeated to find out why it failed.
+                Some(rustemon::error::Error::Reqwest(error)) if error.is_decode() => {
+                    utility::request_status(&url).await.map_or(ErrorKind::Network, ErrorKind::of_status)
+                }
+                Some(error) => ErrorKind::of_rustemon(error),
+                None => ErrorKind::Other,
+            };
+
+            let message = match kind {
+                ErrorKind::Network => format!(
+                    "failed to resolve {name} '{text}' - PokéAPI is unreachable or returning errors, try again later \
+                     ({error})"
+                ),
+                _ => format!("failed to resolve {name} '{text}' - {error}"),
+            };
+
+            bail!(error::Error::new(kind, message).with_query(name, text))
+        }
+    }
+}
+
+/// Returns the name of the resource that most closely resembles the given search text, if any are similar enough.
+async fn suggestion(client: &RustemonClient, resource: &str, text: &str) -> Result<Option<String>> {
+    let entries: Vec<String> = match resource {
+        "ability" => rustemon::pokemon::ability::get_all_entries(client).await?.into_iter().map(|v| v.name).collect(),
+        "ailment" => {
+            rustemon::moves::move_ailment::get_all_entries(client).await?.into_iter().map(|v| v.name).collect()
+        }
+        "characteristic" => rustemon::pokemon::characteristic::get_all_entries(client)
+            .await?
+            .into_iter()
+            .filter_map(|v| Some(lookup::resource_id(&v.url)?.to_string()))
+            .collect(),
+        "contest type" => {
+            rustemon::contests::contest_type::get_all_entries(client).await?.into_iter().map(|v| v.name).collect()
+        }
+        "damage class" => {
+            rustemon::moves::move_damage_class::get_all_entries(client).await?.into_iter().map(|v| v.name).collect()
+        }
+        "game" => rustemon::games::version::get_all_entries(client).await?.into_iter().map(|v| v.name).collect(),
+        "generation" => {
+            rustemon::games::generation::get_all_entries(client).await?.into_iter().map(|v| v.name).collect()
+        }
+        "growth rate" => {
+            rustemon::pokemon::growth_rate::get_all_entries(client).await?.into_iter().map(|v| v.name).collect()
+        }
+        "item" => rustemon::items::item::get_all_entries(client).await?.into_iter().map(|v| v.name).collect(),
+        "item category" => {
+            rustemon::items::item_category::get_all_entries(client).await?.into_iter().map(|v| v.name).collect()
+        }
+        "machine" => rustemon::machines::machine::get_all_entries(client)
+            .await?
+            .into_iter()
+            .filter_map(|v| Some(lookup::resource_id(&v.url)?.to_string()))
+            .collect(),
+        "move" => rustemon::moves::move_::get_all_entries(client).await?.into_iter().map(|v| v.name).collect(),
+        "pokeathlon stat" => {
+            rustemon::pokemon::pokeathlon_stat::get_all_entries(client).await?.into_iter().map(|v| v.name).collect()
+        }
+        "pokedex" => rustemon::games::pokedex::get_all_entries(client).await?.into_iter().map(|v| v.name).collect(),
+        "pokemon" => rustemon::pokemon::pokemon::get_all_entries(client).await?.into_iter().map(|v| v.name).collect(),
+        "species" => {
+            rustemon::pokemon::pokemon_species::get_all_entries(client).await?.into_iter().map(|v| v.name).collect()
+        }
+        "stat" => rustemon::pokemon::stat::get_all_entries(client).await?.into_iter().map(|v| v.name).collect(),
+        "type" => rustemon::pokemon::type_::get_all_entries(client).await?.into_iter().map(|v| v.name).collect(),
+        "version" => rustemon::games::version::get_all_entries(client).await?.into_iter().map(|v| v.name).collect(),
+        _ => return Ok(None),
+    };
+
+    Ok(utility::closest_match(&text.replace(' ', "-").to_lowercase(), &entries).map(str::to_owned))
+}
+
+async fn version_group(arguments: &Arguments, client: &RustemonClient) -> Result<Option<VersionGroup>> {
+    let Some(text) = arguments.version_group.as_deref() else { return Ok(None) };
+    let api_text = text.replace(' ', "-").to_lowercase();
+
+    if let Ok(version_group) = rustemon::games::version_group::get_by_name(&api_text, client).await {
+        return Ok(Some(version_group));
+    }
+
+    let version_groups = rustemon::games::version_group::get_all_entries(client).await?;
+    let version_groups = version_groups.into_iter().map(|v| v.name).collect::<Vec<_>>();
+
+    bail!(error::Error::not_found(format!(
+        "unknown version group '{text}' - expected one of: {}",
+        version_groups.join(", ")
+    )))
+}
+
+async fn generation(client: &RustemonClient, text: &str) -> Result<Generation> {
+    let api_text = text.replace(' ', "-").to_lowercase();
+
+    if let Ok(id) = api_text.parse() {
+        return self::search("generation", text, id, rustemon::games::generation::get_by_id(id, client)).await;
+    }
+
+    let api_text = if api_text.starts_with("generation-") { api_text } else { format!("generation-{api_text}") };
+
+    self::search("generation", text, &api_text, rustemon::games::generation::get_by_name(&api_text, client)).await
+}
+
+/// Returns the names of every version group released in or after the given generation.
+async fn version_groups_since(client: &RustemonClient, text: &str) -> Result<HashSet<String>> {
+    let since = self::generation(client, text).await?;
+    let mut generations = rustemon::games::generation::get_all_entries(client).await?;
+
+    generations.retain(|v| lookup::resource_id(&v.url).is_some_and(|id| id >= since.id));
+
+    let generations = utility::follow_all(client, &generations).await?;
+
+    Ok(generations.into_iter().flat_map(|v| v.version_groups).map(|v| v.name).collect())
+}
+
+async fn generation_suffix(
+    arguments: &Arguments,
+    client: &RustemonClient,
+    generation: &NamedApiResource<Generation>,
+) -> Result<String> {
+    if arguments.no_generation {
+        return Ok(String::new());
+    }
+
+    let generation_name = localized_search(&generation.follow(client).await?.names)?.name.to_owned();
+
+    Ok(format!(" ({generation_name})"))
+}
+
+/// Appends the given identifier to the given name if `--show-id` is set.
+fn with_id(arguments: &Arguments, name: &str, id: i64) -> String {
+    if arguments.show_id { format!("{name} #{id}") } else { name.to_owned() }
+}
+
+async fn version_group_name(client: &RustemonClient, version_group: &VersionGroup) -> Result<String> {
+    let mut version_names = Vec::with_capacity(version_group.versions.len());
+
+    for version in &version_group.versions {
+        version_names.push(localized_search(&version.follow(client).await?.names)?.name.to_owned());
     }
+
+    Ok(version_names.join(" / "))
 }
 
 async fn run_pokemon(arguments: &Arguments, client: RustemonClient, api_text: &str) -> Result<()> {
-    let pokemon =
-        self::search("pokemon", &arguments.text, rustemon::pokemon::pokemon::get_by_name(api_text, &client)).await?;
+    let generation = match arguments.generation.as_deref() {
+        Some(text) => Some(self::generation(&client, text).await?),
+        None => None,
+    };
+    let flavor_version = match arguments.flavor_version.as_deref() {
+        Some(text) => {
+            let api_text = text.replace(' ', "-").to_lowercase();
+
+            Some(
+                self::search("version", text, &api_text, rustemon::games::version::get_by_name(&api_text, &client))
+                    .await?,
+            )
+        }
+        None => None,
+    };
+    // Searches over a range are given the Pokédex number as their text.
+    let text = if arguments.range.is_some() { api_text } else { &arguments.text };
+
+    // The version group is resolved whenever it is given, so that an unknown one is always reported.
+    let version_group = self::version_group(arguments, &client).await?;
+    let flavor_filter = FlavorFilter {
+        version: flavor_version.as_ref(),
+        version_group: version_group.as_ref(),
+        generation: generation.as_ref(),
+    };
+
+    if arguments.species_only {
+        return self::run_species(arguments, &client, text, api_text, flavor_filter).await;
+    }
+
+    let mut pokemon =
+        self::search("pokemon", text, api_text, rustemon::pokemon::pokemon::get_by_name(api_text, &client)).await?;
 
     let species = pokemon.species.follow(&client).await?;
-    let species_name = &english_search(&species.names)?.name;
-    let species_generation = english_search(&species.generation.follow(&client).await?.names)?.name.to_owned();
+    let species_name = &localized_search(&species.names)?.name;
+    let species_generation = self::generation_suffix(arguments, &client, &species.generation).await?;
+
+    let mut pokemon_name = species_name.to_owned();
+    let mut pokemon_types = self::form_types(&pokemon, None);
+
+    if let Some(text) = arguments.form.as_deref() {
+        let form;
 
-    async_println!("{species_name} ({species_generation})\n").await?;
+        (pokemon, form) = self::resolve_form(&client, &species, &pokemon, text).await?;
+        pokemon_types = self::form_types(&pokemon, form.as_ref());
 
-    let mut pokemon_types = pokemon.types.clone();
+        if let Some(form) = form {
+            pokemon_name =
+                localized_search(&form.names).map_or_else(|_| species_name.to_owned(), |v| v.name.to_owned());
+        } else {
+            pokemon_name = self::form_name(&client, &pokemon, species_name).await?;
+        }
+    }
+
+    let pokemon_fields = serde_json::Map::from_iter([
+        ("pokemon".to_owned(), serde_json::Value::from(&*pokemon_name)),
+        ("id".to_owned(), serde_json::Value::from(pokemon.id)),
+    ]);
+    let pokemon_name = self::with_id(arguments, &pokemon_name, pokemon.id);
     let mut pokemon_type_names = Vec::with_capacity(pokemon_types.len());
+    let mut pokemon_type_relations = Vec::with_capacity(pokemon_types.len());
+
+    let mut matchup = self::type_matchup(&client).await?;
+
+    matchup.set_threshold(arguments.threshold);
+    matchup.set_band(arguments.min_multiplier, arguments.max_multiplier);
+    matchup.set_pretty(arguments.pretty);
+    matchup.set_explain(arguments.explain);
+    matchup.set_compact(arguments.compact_matchup);
+    matchup.set_max_rows(arguments.max_matchups.map(NonZeroUsize::get));
+    matchup.set_glyphs(arguments.glyphs());
+
+    let pokemon_types =
+        utility::follow_all(&client, &pokemon_types.into_iter().map(|v| v.type_).collect::<Vec<_>>()).await?;
+    let direction = if arguments.offensive { Direction::Offensive } else { Direction::Defensive };
+
+    matchup.apply_pokemon_types(&pokemon_types, direction)?;
+
+    for type_ in pokemon_types {
+        pokemon_type_names.push(localized_search(&type_.names)?.name.to_owned());
+        pokemon_type_relations.push(type_.damage_relations);
+    }
+
+    match arguments.format {
+        OutputFormat::Csv => return async_print!("{}", matchup.to_csv()).await.map_err(Into::into),
+        OutputFormat::Tsv => return async_print!("{}", matchup.to_tsv()).await.map_err(Into::into),
+        OutputFormat::Json => return async_print!("{}", matchup.to_json()?).await.map_err(Into::into),
+        OutputFormat::Jsonl => {
+            // Each row names its Pokémon, so that the rows of a range search can be told apart.
+            return async_print!("{}", matchup.to_jsonl_with(&pokemon_fields)).await.map_err(Into::into);
+        }
+        OutputFormat::Yaml => return async_print!("{}", matchup.to_yaml()?).await.map_err(Into::into),
+        OutputFormat::Markdown => {
+            async_println!("# {pokemon_name}\n\n**Types:** {}\n", pokemon_type_names.join(", ")).await?;
+
+            if arguments.stats {
+                let stats = self::base_stats(arguments, &client, &pokemon).await?;
+                let rows =
+                    stats.iter().map(|(base_stat, name)| format!("| {} | {base_stat} |", format::markdown_cell(name)));
+
+                async_println!("| Stat | Base |\n| --- | --- |\n{}\n", rows.collect::<Vec<_>>().join("\n")).await?;
+            }
+
+            return async_print!("{}", matchup.to_markdown()).await.map_err(Into::into);
+        }
+        OutputFormat::Html => {
+            let badges = pokemon_type_names.iter().map(|v| format::type_badge(v)).collect::<Vec<_>>();
 
-    pokemon_types.sort_unstable_by_key(|v| v.slot);
+            async_println!("<h1>{}</h1>", format::html_escape(&pokemon_name)).await?;
+            async_println!("<p><strong>Types:</strong> {}</p>", badges.join(" ")).await?;
 
-    let mut matchup = TypeMatchup::new(&client).await?;
+            if arguments.stats {
+                async_println!("<table>\n<tr><th>Stat</th><th>Base</th></tr>").await?;
 
-    for type_ in &pokemon_types {
-        let type_ = type_.type_.follow(&client).await?;
+                for (base_stat, name) in self::base_stats(arguments, &client, &pokemon).await? {
+                    async_println!("<tr><td>{}</td><td>{base_stat}</td></tr>", format::html_escape(&name)).await?;
+                }
 
-        pokemon_type_names.push(english_search(&type_.names)?.name.to_owned());
+                async_println!("</table>").await?;
+            }
 
-        matchup.apply_relations(&type_.damage_relations).await?;
+            return async_print!("{}", matchup.to_html()).await.map_err(Into::into);
+        }
+        OutputFormat::Text => {}
     }
 
-    async_println!("Types:\t{}", pokemon_type_names.join(", ")).await?;
+    if arguments.quiet {
+        let stats = self::base_stats(arguments, &client, &pokemon).await?;
+        let stats = stats.iter().map(|(base_stat, name)| format!("{base_stat} {name}")).collect::<Vec<_>>();
+
+        async_println!("{pokemon_name}\nTypes:\t\t{}", pokemon_type_names.join(", ")).await?;
+
+        return async_println!("Stats:\t\t{}", stats.join(", ")).await.map_err(Into::into);
+    }
+
+    async_println!("{pokemon_name}{species_generation}\n").await?;
+
+    if arguments.all_languages {
+        self::print_names(&client, &species.names).await?;
+    }
+
+    if arguments.sprite {
+        let mut sprite = pokemon.sprites.front_default.as_deref();
+
+        if arguments.shiny {
+            if let Some(shiny) = pokemon.sprites.front_shiny.as_deref() {
+                sprite = Some(shiny);
+            } else {
+                async_eprintln!("warning: no shiny sprite available, falling back to the default sprite").await?;
+            }
+        }
+
+        self::print_sprite(arguments, sprite).await?;
+    }
+
+    async_println!("Types:\t\t{}", pokemon_type_names.join(", ")).await?;
+
+    if species.varieties.len() > 1 {
+        let mut form_names = Vec::with_capacity(species.varieties.len());
+
+        for variety in &species.varieties {
+            let form_name = self::form_name(&client, &variety.pokemon.follow(&client).await?, species_name).await?;
+
+            form_names.push(if variety.is_default { format!("{form_name} (default)") } else { form_name });
+        }
+
+        async_println!("Forms:\t\t{}", form_names.join(", ")).await?;
+    }
 
     let pokemon_weight = pokemon.weight as f64 / 10.0;
 
-    async_println!("Weight:\t{pokemon_weight} kg\n").await?;
+    async_println!("Weight:\t\t{pokemon_weight} kg").await?;
 
-    matchup.print().await
+    if let Some(habitat) = &species.habitat {
+        let species_habitat = localized_search(&habitat.follow(&client).await?.names)?.name.to_owned();
+
+        async_println!("Habitat:\t{species_habitat}").await?;
+    } else {
+        async_println!("Habitat:\t-").await?;
+    }
+
+    if let Some(shape) = &species.shape {
+        let species_shape = localized_search(&shape.follow(&client).await?.names)?.name.to_owned();
+
+        async_println!("Shape:\t\t{species_shape}").await?;
+    } else {
+        async_println!("Shape:\t\t-").await?;
+    }
+
+    let species_color = localized_search(&species.color.follow(&client).await?.names)?.name.to_owned();
+
+    async_println!("Color:\t\t{species_color}").await?;
+
+    async_println!("Moves:\t\t{} learnable", pokemon.moves.len()).await?;
+
+    match pokemon.base_experience.filter(|v| *v > 0) {
+        Some(pokemon_experience) => async_println!("Base EXP:\t{pokemon_experience}\n").await?,
+        None => async_println!("Base EXP:\t-\n").await?,
+    }
+
+    if arguments.dex {
+        let mut pokedex_lines = Vec::with_capacity(species.pokedex_numbers.len());
+
+        for entry in &species.pokedex_numbers {
+            let pokedex_name = localized_search(&entry.pokedex.follow(&client).await?.names)?.name.to_owned();
+
+            pokedex_lines.push(format!("#{}\t{pokedex_name}", entry.entry_number));
+        }
+
+        async_println!("{}\n{}\n", arguments.glyphs().fold("Pokédex Numbers:"), pokedex_lines.join("\n")).await?;
+    }
+
+    if arguments.stats {
+        let stat_lines = self::base_stats(arguments, &client, &pokemon).await?;
+        let stat_lines = stat_lines.iter().map(|(base_stat, name)| format!("{base_stat}\t{name}"));
+
+        async_println!("Base Stats:\n{}\n", stat_lines.collect::<Vec<_>>().join("\n")).await?;
+    }
+
+    if arguments.relations {
+        for (type_name, relations) in pokemon_type_names.iter().zip(&pokemon_type_relations) {
+            let strong_against = matchup.type_names(&relations.double_damage_to).join(", ");
+            let weak_to = matchup.type_names(&relations.double_damage_from).join(", ");
+
+            async_println!("{type_name}:").await?;
+            async_println!("Strong against:\t{}", if strong_against.is_empty() { "-" } else { &strong_against })
+                .await?;
+            async_println!("Weak to:\t{}\n", if weak_to.is_empty() { "-" } else { &weak_to }).await?;
+        }
+    }
+
+    async_print!("{}", matchup.to_text()).await?;
+
+    if arguments.flavor {
+        self::print_flavor(arguments, &client, &species, flavor_filter).await?;
+    }
+
+    if arguments.locations {
+        self::print_locations(&client, &pokemon, version_group.as_ref()).await?;
+    }
+
+    if arguments.moves {
+        let since = match arguments.since.as_deref() {
+            Some(text) => Some(self::version_groups_since(&client, text).await?),
+            None => None,
+        };
+
+        self::print_learnset(
+            &client,
+            &pokemon.moves,
+            version_group,
+            generation.as_ref(),
+            since.as_ref(),
+            arguments.up_to_level,
+        )
+        .await?;
+    }
+
+    Ok(())
 }
 
-async fn run_ability(arguments: &Arguments, client: RustemonClient, api_text: &str) -> Result<()> {
-    let ability =
-        self::search("ability", &arguments.text, rustemon::pokemon::ability::get_by_name(api_text, &client)).await?;
+/// Prints a species' overview and Pokédex entry, without following its types or any of its Pokémon.
+async fn run_species(
+    arguments: &Arguments,
+    client: &RustemonClient,
+    text: &str,
+    api_text: &str,
+    flavor_filter: FlavorFilter<'_>,
+) -> Result<()> {
+    let species =
+        self::search("species", text, api_text, rustemon::pokemon::pokemon_species::get_by_name(api_text, client))
+            .await?;
+    let species_name = self::with_id(arguments, &localized_search(&species.names)?.name, species.id);
+    let species_generation = self::generation_suffix(arguments, client, &species.generation).await?;
+
+    async_println!("{species_name}{species_generation}\n").await?;
+
+    if arguments.all_languages {
+        self::print_names(client, &species.names).await?;
+    }
 
-    let ability_name = &english_search(&ability.names)?.name;
-    let ability_generation = english_search(&ability.generation.follow(&client).await?.names)?.name.to_owned();
-    let ability_effect = &english_search_by(&ability.effect_entries, |v| &v.language)?.effect;
+    match localized_search_by(&species.genera, |v| &v.language) {
+        Ok(genus) => async_println!("Classification:\t{}", genus.genus).await?,
+        Err(_) => async_println!("Classification:\t-").await?,
+    }
 
-    async_println!("{ability_name} ({ability_generation})\n\n---\n\n{ability_effect}").await.map_err(Into::into)
+    self::print_flavor(arguments, client, &species, flavor_filter).await
 }
 
-async fn run_move(arguments: &Arguments, client: RustemonClient, api_text: &str) -> Result<()> {
-    let move_ = self::search("move", &arguments.text, rustemon::moves::move_::get_by_name(api_text, &client)).await?;
+/// The versions that a Pokédex entry is preferably taken from, in order of preference.
+#[derive(Clone, Copy, Debug)]
+struct FlavorFilter<'f> {
+    /// The version given by `--flavor-version`.
+    version: Option<&'f Version>,
+    /// The version group given by `--version-group`.
+    version_group: Option<&'f VersionGroup>,
+    /// The generation given by `--generation`.
+    generation: Option<&'f Generation>,
+}
 
-    let move_name = &english_search(&move_.names)?.name;
-    let move_generation = english_search(&move_.generation.follow(&client).await?.names)?.name.to_owned();
+/// Prints the species' Pokédex entry, preferring the given version, version group, or generation.
+async fn print_flavor(
+    arguments: &Arguments,
+    client: &RustemonClient,
+    species: &PokemonSpecies,
+    filter: FlavorFilter<'_>,
+) -> Result<()> {
+    let entries = utility::localized_entries(&species.flavor_text_entries, |v| &v.language);
+    let mut entry = None;
 
-    async_println!("{move_name} ({move_generation})\n").await?;
+    if let Some(version) = filter.version {
+        entry =
+            language::linear_search_strict(&entries, |v| v.version.as_ref().is_some_and(|v| v.name == version.name))
+                .ok()
+                .copied();
 
-    let move_class = english_search(&move_.damage_class.follow(&client).await?.names)?.name.to_owned();
-    let move_class = move_class.chars().take(1).map(|c| c.to_ascii_uppercase()).chain(move_class.chars().skip(1));
+        if entry.is_none() {
+            let version_name = &localized_search(&version.names)?.name;
 
-    async_println!("Class:\t\t{}", move_class.collect::<Box<str>>()).await?;
+            let warning = format!("warning: no Pokédex entry for {version_name}, falling back to the newest entry");
 
-    let move_type = english_search(&move_.type_.follow(&client).await?.names)?.name.to_owned();
+            async_eprintln!("{}", arguments.glyphs().fold(&warning)).await?;
+        }
+    }
 
-    async_println!("Type:\t\t{move_type}").await?;
+    if let Some(version_group) = filter.version_group.filter(|_| entry.is_none()) {
+        let is_in_group = |v: &NamedApiResource<Version>| version_group.versions.iter().any(|g| g.name == v.name);
 
-    if let Some(move_pp) = move_.pp {
-        async_println!("PP:\t\t{move_pp}").await?;
-    } else {
-        async_println!("PP:\t\t-").await?;
+        entry = entries.iter().copied().rfind(|v| v.version.as_ref().is_some_and(is_in_group));
+
+        if entry.is_none() {
+            let version_group_name = self::version_group_name(client, version_group).await?;
+
+            let warning =
+                format!("warning: no Pokédex entry for {version_group_name}, falling back to the newest entry");
+
+            async_eprintln!("{}", arguments.glyphs().fold(&warning)).await?;
+        }
     }
 
-    if let Some(move_power) = move_.power {
-        async_println!("Power:\t\t{move_power}").await?;
-    } else {
-        async_println!("Power:\t\t-").await?;
+    let entry = match entry {
+        Some(entry) => Some(entry),
+        None => self::flavor_text(client, &entries, filter.generation).await?,
+    };
+
+    match entry {
+        Some(entry) => {
+            let flavor_text = utility::normalize_whitespace(&entry.flavor_text);
+
+            async_println!("\n---\n\n{}", utility::wrap_text(&flavor_text, arguments.width)).await?;
+        }
+        None => async_println!("\n---\n\n{}", arguments.glyphs().fold("No Pokédex entry available")).await?,
     }
 
-    if let Some(move_accuracy) = move_.accuracy {
-        async_println!("Accuracy:\t{move_accuracy}").await?;
-    } else {
-        async_println!("Accuracy:\t-").await?;
+    Ok(())
+}
+
+/// Returns the Pokémon's base stats alongside their names, followed by their total.
+async fn base_stats(arguments: &Arguments, client: &RustemonClient, pokemon: &Pokemon) -> Result<Vec<(i64, String)>> {
+    let mut stats = Vec::with_capacity(pokemon.stats.len() + 1);
+
+    for stat in &pokemon.stats {
+        let stat_name = localized_search(&stat.stat.follow(client).await?.names)?.name.to_owned();
+
+        stats.push((stat.base_stat, stat_name));
     }
 
-    if move_.priority != 0 {
-        async_println!("Priority:\t{}", move_.priority).await?;
+    if arguments.sort_stats {
+        stats.sort_by_key(|(base_stat, _)| Reverse(*base_stat));
     }
 
-    let move_target = english_search(&move_.target.follow(&client).await?.names)?.name.to_owned();
-    let move_effect = &english_search_by(&move_.effect_entries, |v| &v.language)?.effect;
+    stats.push((pokemon.stats.iter().map(|v| v.base_stat).sum(), "Total".to_owned()));
 
-    async_println!("Target:\t\t{move_target}\n\n---\n\n{move_effect}").await.map_err(Into::into)
+    Ok(stats)
 }
 
-async fn run_item(arguments: &Arguments, client: RustemonClient, api_text: &str) -> Result<()> {
-    let item = self::search("item", &arguments.text, rustemon::items::item::get_by_name(api_text, &client)).await?;
+async fn run_pokemon_range(arguments: &Arguments, client: RustemonClient) -> Result<()> {
+    /// The largest range that may be searched without `--force`.
+    const MAX_RANGE: i64 = 20;
+
+    let text = arguments.range.as_deref().unwrap_or_default();
+    let Some(range) = utility::parse_range(text) else {
+        bail!(error::Error::invalid_arguments(format!("invalid range '{text}' - expected a range such as '1-9'")));
+    };
 
-    let item_name = &english_search(&item.names)?.name;
-    let item_category = english_search(&item.category.follow(&client).await?.names)?.name.to_owned();
+    if range.end() - range.start() + 1 > MAX_RANGE && !arguments.force {
+        bail!(error::Error::invalid_arguments(format!(
+            "the range '{text}' exceeds {MAX_RANGE} Pokémon - pass --force to search it anyway"
+        )));
+    }
 
-    async_println!("{item_name} ({item_category})\n\n---\n").await?;
+    // The client cannot be cloned, so each search after the first is given a new client sharing the same cache.
+    let mut client = Some(client);
+    let mut failure = None;
 
-    if let Some((item_fling_effect, item_fling_power)) = item.fling_effect.zip(item.fling_power) {
-        let item_fling_effect = item_fling_effect.follow(&client).await?.effect_entries;
-        let item_fling_effect = &english_search_by(&item_fling_effect, |v| &v.language)?.effect;
+    for id in range {
+        let client = match client.take() {
+            Some(client) => client,
+            None => {
+                if arguments.format == OutputFormat::Text {
+                    async_println!("\n===\n").await?;
+                }
 
-        async_println!("Thrown with fling ({item_fling_power} power)\n:   {item_fling_effect}\n").await?;
+                self::client(arguments)?
+            }
+        };
+
+        // A failed search is reported in place, so that the remaining Pokémon are still searched.
+        if let Err(error) = self::run_pokemon(arguments, client, &id.to_string()).await {
+            self::emit_error(arguments, &error).await?;
+
+            failure = Some(error);
+        }
+
+        // Each search is flushed as it finishes, so that streaming consumers receive its lines immediately.
+        tokio::io::stdout().flush().await?;
     }
 
-    let item_effect = &english_search_by(&item.effect_entries, |v| &v.language)?.effect;
+    // The exit status reflects the last failure, which has already been reported.
+    failure.map_or(Ok(()), |error| Err(error).context(error::Reported))
+}
+
+/// Returns the types of the given Pokémon ordered by slot, preferring those of the given form if it overrides them.
+fn form_types(pokemon: &Pokemon, form: Option<&PokemonForm>) -> Vec<PokemonType> {
+    let mut types = match form.filter(|v| !v.types.is_empty()) {
+        Some(form) => form.types.iter().map(|v| PokemonType { slot: v.slot, type_: v.type_.clone() }).collect(),
+        None => pokemon.types.clone(),
+    };
 
-    async_println!("{item_effect}").await.map_err(Into::into)
+    types.sort_unstable_by_key(|v| v.slot);
+
+    types
 }
 
-async fn run_type(_: &Arguments, client: RustemonClient, api_text: &str) -> Result<()> {
-    let mut types = api_text.split(',').collect::<Vec<_>>();
-    let mut matchup = TypeMatchup::new(&client).await?;
+async fn resolve_form(
+    client: &RustemonClient,
+    species: &PokemonSpecies,
+    pokemon: &Pokemon,
+    text: &str,
+) -> Result<(Pokemon, Option<PokemonForm>)> {
+    let api_text = text.replace(' ', "-").to_lowercase();
+    let full_api_text = format!("{}-{api_text}", species.name);
+    let is_match = |name: &str| name == api_text || name == full_api_text;
+
+    if let Some(variety) = species.varieties.iter().find(|v| is_match(&v.pokemon.name)) {
+        return Ok((variety.pokemon.follow(client).await?, None));
+    }
+
+    if let Some(form) = pokemon.forms.iter().find(|v| is_match(&v.name)) {
+        return Ok((pokemon.clone(), Some(form.follow(client).await?)));
+    }
 
-    types.dedup();
+    let varieties = species.varieties.iter().map(|v| &*v.pokemon.name);
+    let forms =
+        pokemon.forms.iter().map(|v| &*v.name).filter(|v| species.varieties.iter().all(|p| p.pokemon.name != *v));
 
-    for type_ in &types {
-        let type_ = self::search("type", type_, rustemon::pokemon::type_::get_by_name(type_, &client)).await?;
+    bail!(error::Error::not_found(format!(
+        "unknown form '{text}' - expected one of: {}",
+        varieties.chain(forms).collect::<Vec<_>>().join(", ")
+    )))
+}
+
+async fn form_name(client: &RustemonClient, pokemon: &Pokemon, species_name: &str) -> Result<String> {
+    let Some(form) = pokemon.forms.first() else { return Ok(species_name.to_owned()) };
+    let form = form.follow(client).await?;
+
+    Ok(localized_search(&form.names).map_or_else(|_| species_name.to_owned(), |v| v.name.to_owned()))
+}
+
+async fn flavor_text<'f>(
+    client: &RustemonClient,
+    entries: &[&'f FlavorText],
+    generation: Option<&Generation>,
+) -> Result<Option<&'f FlavorText>> {
+    let mut entries = entries.to_vec();
+
+    entries.sort_by_key(|v| Reverse(v.version.as_ref().and_then(|v| lookup::resource_id(&v.url))));
+
+    let Some(generation) = generation else { return Ok(entries.first().copied()) };
+
+    for entry in entries {
+        let Some(version) = &entry.version else { continue };
+
+        if utility::is_in_generation(generation, &version.follow(client).await?.version_group) {
+            return Ok(Some(entry));
+        }
+    }
+
+    Ok(None)
+}
+
+async fn print_sprite(arguments: &Arguments, url: Option<&str>) -> Result<()> {
+    let Some(url) = url else {
+        return async_println!("No sprite available\n").await.map_err(Into::into);
+    };
+
+    // Sprites are drawn entirely with color escapes, so they are skipped rather than printed as noise.
+    if !utility::use_color(&std::io::stdout()) {
+        return async_println!("Sprite hidden as color is disabled\n").await.map_err(Into::into);
+    }
+
+    let sprite = sprite::fetch(&arguments.cache_dir, url).await?;
+
+    async_println!("{}", sprite::render(&sprite, sprite::MAX_COLUMNS, arguments.glyphs())).await.map_err(Into::into)
+}
+
+fn page<'e, T>(arguments: &Arguments, entries: &'e [T]) -> Result<(&'e [T], Option<String>)> {
+    let entries = &entries[.. entries.len().min(arguments.limit.unwrap_or(usize::MAX))];
+    let Some(page) = arguments.page else { return Ok((entries, None)) };
+
+    let Some((entries, page_count)) = utility::paginate(entries, page.get(), arguments.page_size.get()) else {
+        bail!(error::Error::invalid_arguments(format!("page {page} is out of range")));
+    };
 
-        matchup.apply_relations(&type_.damage_relations).await?;
+    Ok((entries, Some(format!("page {page}/{page_count}"))))
+}
+
+async fn print_page_footer(footer: Option<String>) -> Result<()> {
+    let Some(footer) = footer else { return Ok(()) };
+
+    async_println!("\n{footer}").await.map_err(Into::into)
+}
+
+async fn print_names(client: &RustemonClient, names: &[Name]) -> Result<()> {
+    let mut lines = Vec::with_capacity(names.len());
+
+    for name in names {
+        let language = name.language.follow(client).await?;
+        let language_name = localized_search(&language.names).map_or(&*language.name, |v| &*v.name);
+
+        lines.push(format!("{language_name}:\t{}", name.name));
+    }
+
+    async_println!("Names:\n{}\n", lines.join("\n")).await.map_err(Into::into)
+}
+
+async fn print_locations(
+    client: &RustemonClient,
+    pokemon: &Pokemon,
+    version_group: Option<&VersionGroup>,
+) -> Result<()> {
+    let encounters = rustemon::pokemon::pokemon::encounters::get_by_id(pokemon.id, client).await?;
+    let mut lines = Vec::new();
+
+    for encounter in &encounters {
+        let area = encounter.location_area.follow(client).await?;
+        let area_name = localized_search(&area.names).map_or_else(|_| area.name.clone(), |v| v.name.clone());
+        let mut levels = BTreeMap::<(String, String, String), (i64, i64, i64)>::new();
+
+        for details in &encounter.version_details {
+            if version_group.is_some_and(|g| g.versions.iter().all(|v| v.name != details.version.name)) {
+                continue;
+            }
+
+            let version_name = localized_search(&details.version.follow(client).await?.names)?.name.to_owned();
+
+            for entry in &details.encounter_details {
+                let method_name = localized_search(&entry.method.follow(client).await?.names)?.name.to_owned();
+                let mut condition_names = Vec::with_capacity(entry.condition_values.len());
+
+                for condition in &entry.condition_values {
+                    condition_names.push(localized_search(&condition.follow(client).await?.names)?.name.to_owned());
+                }
+
+                let key = (method_name, condition_names.join(", "), version_name.clone());
+                let (min_level, max_level, chance) = levels.entry(key).or_insert((entry.min_level, entry.max_level, 0));
+
+                *min_level = (*min_level).min(entry.min_level);
+                *max_level = (*max_level).max(entry.max_level);
+                *chance += entry.chance;
+            }
+        }
+
+        // Encounters that only differ by their conditions are listed together.
+        let mut groups = BTreeMap::<(String, i64, i64, i64), BTreeMap<String, Vec<String>>>::new();
+
+        for ((method_name, conditions, version_name), (min_level, max_level, chance)) in levels {
+            let group = groups.entry((method_name, min_level, max_level, chance)).or_default();
+
+            group.entry(conditions).or_default().push(version_name);
+        }
+
+        if groups.is_empty() {
+            continue;
+        }
+
+        lines.push(format!("\n{area_name}:"));
+
+        for ((method_name, min_level, max_level, chance), conditions) in groups {
+            let levels =
+                if min_level == max_level { format!("{min_level}") } else { format!("{min_level}-{max_level}") };
+
+            for (conditions, version_names) in conditions {
+                let suffix = if conditions.is_empty() { String::new() } else { format!(" - {conditions}") };
+
+                lines.push(format!("{method_name}, Lv. {levels}, {chance}% ({}){suffix}", version_names.join(", ")));
+            }
+        }
+    }
+
+    if lines.is_empty() {
+        return async_println!("\n---\n\nNo wild encounters").await.map_err(Into::into);
     }
 
-    matchup.print().await
+    async_println!("\n---\n\nLocations\n{}", lines.join("\n")).await.map_err(Into::into)
+}
+
+async fn print_learnset(
+    client: &RustemonClient,
+    moves: &[PokemonMove],
+    version_group: Option<VersionGroup>,
+    generation: Option<&Generation>,
+    since: Option<&HashSet<String>>,
+    up_to_level: Option<i64>,
+) -> Result<()> {
+    let latest_version_group = utility::latest_version_group(moves, |v| {
+        generation.is_none_or(|g| utility::is_in_generation(g, v)) && since.is_none_or(|s| s.contains(&v.name))
+    });
+
+    let version_group = match (version_group, latest_version_group) {
+        (Some(version_group), _) => version_group,
+        (None, Some(version_group)) => version_group.follow(client).await?,
+        (None, None) => return async_println!("\n---\n\nNo learnable moves").await.map_err(Into::into),
+    };
+
+    let version_names = self::version_group_name(client, &version_group).await?;
+    let mut learnset = utility::group_learnset(moves, &version_group.name);
+
+    if let Some(up_to_level) = up_to_level {
+        if let Some(list) = learnset.get_mut(&LearnMethod::LevelUp) {
+            list.retain(|(level, _)| *level <= up_to_level);
+        }
+
+        learnset.retain(|_, list| !list.is_empty());
+    }
+
+    if learnset.is_empty() {
+        return async_println!("\n---\n\nNo learnable moves in {version_names}").await.map_err(Into::into);
+    }
+
+    async_println!("\n---\n\nMoves ({version_names})").await?;
+
+    for (method, list) in learnset {
+        async_println!("\n{}:", method.label()).await?;
+
+        for (level, move_) in list {
+            let move_name = localized_search(&move_.follow(client).await?.names)?.name.to_owned();
+
+            if method == LearnMethod::LevelUp {
+                async_println!("Lv. {level}\t{move_name}").await?;
+            } else {
+                async_println!("\t{move_name}").await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn run_ability(arguments: &Arguments, client: RustemonClient, api_text: &str) -> Result<()> {
+    let ability =
+        self::search("ability", &arguments.text, api_text, rustemon::pokemon::ability::get_by_name(api_text, &client))
+            .await?;
+
+    let ability_name = &self::with_id(arguments, &localized_search(&ability.names)?.name, ability.id);
+    let ability_generation = self::generation_suffix(arguments, &client, &ability.generation).await?;
+
+    if let OutputFormat::Markdown | OutputFormat::Html = arguments.format {
+        let ability_effect = localized_search_by(&ability.effect_entries, |v| &v.language)?;
+        let ability_effect = utility::effect_text(ability_effect, arguments.short);
+
+        return self::print_formatted_effect(arguments, ability_name, &[], ability_effect).await;
+    }
+
+    if arguments.quiet {
+        if arguments.hide_effect {
+            return async_println!("{ability_name}").await.map_err(Into::into);
+        }
+
+        let ability_effect = localized_search_by(&ability.effect_entries, |v| &v.language)?;
+        let ability_effect = utility::wrap_text(utility::effect_text(ability_effect, arguments.short), arguments.width);
+
+        return async_println!("{ability_name}\n{ability_effect}").await.map_err(Into::into);
+    }
+
+    async_println!("{ability_name}{ability_generation}\n").await?;
+
+    if !ability.is_main_series {
+        async_println!("Only appears outside of the main series games\n").await?;
+    }
+
+    if arguments.all_languages {
+        self::print_names(&client, &ability.names).await?;
+    }
+
+    if arguments.flavor {
+        let ability_flavor = utility::localized_entries(&ability.flavor_text_entries, |v| &v.language)
+            .into_iter()
+            .max_by_key(|v| lookup::resource_id(&v.version_group.url));
+
+        match ability_flavor {
+            Some(entry) => {
+                let flavor_text = utility::normalize_whitespace(&entry.flavor_text);
+
+                async_println!("{}\n", utility::wrap_text(&flavor_text, arguments.width)).await?;
+            }
+            None => async_println!("No flavor text available\n").await?,
+        }
+    }
+
+    if arguments.show_effect_changes {
+        self::print_ability_history(arguments, &client, &ability.effect_changes).await?;
+    }
+
+    if arguments.hide_effect {
+        return Ok(());
+    }
+
+    let ability_effect = localized_search_by(&ability.effect_entries, |v| &v.language)?;
+    let ability_effect = utility::wrap_text(utility::effect_text(ability_effect, arguments.short), arguments.width);
+
+    async_println!("---\n\n{ability_effect}").await.map_err(Into::into)
+}
+
+async fn print_ability_history(
+    arguments: &Arguments,
+    client: &RustemonClient,
+    effect_changes: &[AbilityEffectChange],
+) -> Result<()> {
+    if effect_changes.is_empty() {
+        return async_println!("No changes in previous versions\n").await.map_err(Into::into);
+    }
+
+    let mut effect_changes = effect_changes.iter().collect::<Vec<_>>();
+
+    effect_changes.sort_by_key(|v| lookup::resource_id(&v.version_group.url));
+
+    for change in effect_changes {
+        let version_names = self::version_group_name(client, &change.version_group.follow(client).await?).await?;
+        let change_effect = &localized_search_by(&change.effect_entries, |v| &v.language)?.effect;
+
+        async_println!("Before {version_names}:\n{}\n", utility::wrap_text(change_effect, arguments.width)).await?;
+    }
+
+    Ok(())
+}
+
+async fn run_move(arguments: &Arguments, client: RustemonClient, api_text: &str) -> Result<()> {
+    let move_ =
+        self::search("move", &arguments.text, api_text, rustemon::moves::move_::get_by_name(api_text, &client)).await?;
+
+    let move_name = &self::with_id(arguments, &localized_search(&move_.names)?.name, move_.id);
+    let move_generation = self::generation_suffix(arguments, &client, &move_.generation).await?;
+
+    if let OutputFormat::Markdown | OutputFormat::Html = arguments.format {
+        let move_type = localized_search(&move_.type_.follow(&client).await?.names)?.name.to_owned();
+        let fields = [
+            ("Type", move_type),
+            ("PP", move_.pp.map_or_else(|| "-".to_owned(), |v| v.to_string())),
+            ("Power", move_.power.map_or_else(|| "-".to_owned(), |v| v.to_string())),
+            ("Accuracy", utility::format_accuracy(move_.accuracy, &move_.damage_class.name)),
+        ];
+
+        return self::print_formatted_effect(arguments, move_name, &fields, &self::move_effect(arguments, &move_)?)
+            .await;
+    }
+
+    if arguments.quiet {
+        async_println!("{move_name}").await?;
+    } else {
+        async_println!("{move_name}{move_generation}\n").await?;
+
+        if arguments.all_languages {
+            self::print_names(&client, &move_.names).await?;
+        }
+
+        let move_class = localized_search(&move_.damage_class.follow(&client).await?.names)?.name.to_owned();
+        let move_class = move_class.chars().take(1).map(|c| c.to_ascii_uppercase()).chain(move_class.chars().skip(1));
+
+        async_println!("Class:\t\t{}", move_class.collect::<Box<str>>()).await?;
+    }
+
+    let move_type = localized_search(&move_.type_.follow(&client).await?.names)?.name.to_owned();
+
+    async_println!("Type:\t\t{move_type}").await?;
+
+    if !arguments.quiet {
+        if let Some(move_pp) = move_.pp {
+            async_println!("PP:\t\t{move_pp}").await?;
+        } else {
+            async_println!("PP:\t\t-").await?;
+        }
+    }
+
+    if let Some(move_power) = move_.power {
+        async_println!("Power:\t\t{move_power}").await?;
+    } else {
+        async_println!("Power:\t\t-").await?;
+    }
+
+    async_println!("Accuracy:\t{}", utility::format_accuracy(move_.accuracy, &move_.damage_class.name)).await?;
+
+    if arguments.quiet {
+        return Ok(());
+    }
+
+    if move_.priority != 0 {
+        async_println!("Priority:\t{}", utility::format_priority(move_.priority)).await?;
+    }
+
+    let move_target = move_.target.follow(&client).await?;
+    let move_target_description = &localized_search_by(&move_target.descriptions, |v| &v.language)?.description;
+    let move_target = &localized_search(&move_target.names)?.name;
+
+    async_println!("Target:\t\t{move_target}\n\t\t{move_target_description}").await?;
+
+    let is_spread = move_.damage_class.name != "status" && utility::is_spread_target(&move_.target.name);
+
+    if (arguments.verbose || arguments.battle_notes) && is_spread {
+        async_println!("Note:\t\tspread move: 0.75{} damage in doubles", arguments.glyphs().times).await?;
+    }
+
+    if arguments.history {
+        self::print_move_history(&client, &move_.past_values).await?;
+    }
+
+    if arguments.hide_effect {
+        return Ok(());
+    }
+
+    let move_effect = self::move_effect(arguments, &move_)?;
+    let move_effect = utility::wrap_text(&move_effect, arguments.width);
+
+    async_println!("\n---\n\n{move_effect}").await.map_err(Into::into)
+}
+
+fn move_effect(arguments: &Arguments, move_: &Move) -> Result<String> {
+    let move_effect_entry = localized_search_by(&move_.effect_entries, |v| &v.language)?;
+    let move_effect = utility::effect_text(move_effect_entry, arguments.short);
+
+    Ok(utility::substitute_effect_chance(move_effect, move_.effect_chance))
+}
+
+/// Removes every move with a power outside of the range given by `--min-power` and `--max-power`.
+async fn retain_power(
+    arguments: &Arguments,
+    client: &RustemonClient,
+    moves: &mut Vec<NamedApiResource<Move>>,
+) -> Result<()> {
+    if arguments.min_power.is_none() && arguments.max_power.is_none() {
+        return Ok(());
+    }
+
+    let mut powers = utility::follow_all(client, moves).await?.into_iter().map(|v| v.power);
+
+    moves.retain(|_| match powers.next().flatten() {
+        Some(power) => arguments.min_power.is_none_or(|v| power >= v) && arguments.max_power.is_none_or(|v| power <= v),
+        None => arguments.include_status,
+    });
+
+    Ok(())
+}
+
+/// Prints a resource's fields and effect in the Markdown or HTML output format.
+async fn print_formatted_effect(
+    arguments: &Arguments,
+    name: &str,
+    fields: &[(&str, String)],
+    effect: &str,
+) -> Result<()> {
+    if arguments.format != OutputFormat::Html {
+        return self::print_markdown_effect(name, fields, effect).await;
+    }
+
+    async_println!("<h1>{}</h1>", format::html_escape(name)).await?;
+
+    if !fields.is_empty() {
+        let header = fields.iter().map(|(label, _)| format!("<th>{}</th>", format::html_escape(label)));
+        let values = fields.iter().map(|(_, value)| format!("<td>{}</td>", format::html_escape(value)));
+
+        async_println!("<table>\n<tr>{}</tr>", header.collect::<String>()).await?;
+        async_println!("<tr>{}</tr>\n</table>", values.collect::<String>()).await?;
+    }
+
+    for paragraph in effect.split("\n\n").map(str::trim).filter(|v| !v.is_empty()) {
+        async_println!("<p>{}</p>", format::html_escape(paragraph)).await?;
+    }
+
+    Ok(())
+}
+
+async fn print_markdown_effect(name: &str, fields: &[(&str, String)], effect: &str) -> Result<()> {
+    async_println!("# {name}\n").await?;
+
+    if !fields.is_empty() {
+        let header = fields.iter().map(|(label, _)| *label).collect::<Vec<_>>();
+        let values = fields.iter().map(|(_, value)| format::markdown_cell(value)).collect::<Vec<_>>();
+
+        async_println!("| {} |", header.join(" | ")).await?;
+        async_println!("|{}", " --- |".repeat(fields.len())).await?;
+        async_println!("| {} |\n", values.join(" | ")).await?;
+    }
+
+    async_println!("```text\n{effect}\n```").await.map_err(Into::into)
+}
+
+async fn print_move_history(client: &RustemonClient, past_values: &[PastMoveStatValues]) -> Result<()> {
+    if past_values.is_empty() {
+        return async_println!("\nNo changes in previous versions").await.map_err(Into::into);
+    }
+
+    let mut past_values = past_values.iter().collect::<Vec<_>>();
+
+    past_values.sort_by_key(|v| lookup::resource_id(&v.version_group.url));
+
+    for values in past_values {
+        let version_names = self::version_group_name(client, &values.version_group.follow(client).await?).await?;
+
+        async_println!("\nBefore {version_names}:").await?;
+
+        if let Some(type_) = &values.type_ {
+            let type_name = localized_search(&type_.follow(client).await?.names)?.name.to_owned();
+
+            async_println!("Type:\t\t{type_name}").await?;
+        }
+
+        if let Some(pp) = values.pp {
+            async_println!("PP:\t\t{pp}").await?;
+        }
+
+        if let Some(power) = values.power {
+            async_println!("Power:\t\t{power}").await?;
+        }
+
+        if let Some(accuracy) = values.accuracy {
+            async_println!("Accuracy:\t{accuracy}").await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn run_item(arguments: &Arguments, client: RustemonClient, api_text: &str) -> Result<()> {
+    let item =
+        self::search("item", &arguments.text, api_text, rustemon::items::item::get_by_name(api_text, &client)).await?;
+
+    let item_name = &self::with_id(arguments, &localized_search(&item.names)?.name, item.id);
+    let item_category = localized_search(&item.category.follow(&client).await?.names)?.name.to_owned();
+
+    if let OutputFormat::Markdown | OutputFormat::Html = arguments.format {
+        let item_effect = localized_search_by(&item.effect_entries, |v| &v.language)?;
+        let item_effect = utility::effect_text(item_effect, arguments.short);
+
+        return self::print_formatted_effect(arguments, item_name, &[("Category", item_category)], item_effect).await;
+    }
+
+    if arguments.hide_effect {
+        if arguments.quiet {
+            return async_println!("{item_name}").await.map_err(Into::into);
+        }
+
+        async_println!("{item_name} ({item_category})\n").await?;
+
+        if arguments.all_languages {
+            self::print_names(&client, &item.names).await?;
+        }
+
+        if arguments.sprite {
+            self::print_sprite(arguments, item.sprites.default.as_deref()).await?;
+        }
+
+        return Ok(());
+    }
+
+    let item_effect = localized_search_by(&item.effect_entries, |v| &v.language)?;
+    let item_effect = utility::wrap_text(utility::effect_text(item_effect, arguments.short), arguments.width);
+
+    if arguments.quiet {
+        return async_println!("{item_name}\n{item_effect}").await.map_err(Into::into);
+    }
+
+    async_println!("{item_name} ({item_category})\n").await?;
+
+    if arguments.all_languages {
+        self::print_names(&client, &item.names).await?;
+    }
+
+    async_println!("---\n").await?;
+
+    if arguments.sprite {
+        self::print_sprite(arguments, item.sprites.default.as_deref()).await?;
+    }
+
+    if let Some((item_fling_effect, item_fling_power)) = item.fling_effect.zip(item.fling_power) {
+        let item_fling_effect = item_fling_effect.follow(&client).await?.effect_entries;
+        let item_fling_effect = &localized_search_by(&item_fling_effect, |v| &v.language)?.effect;
+
+        async_println!("Thrown with fling ({item_fling_power} power)\n:   {item_fling_effect}\n").await?;
+    }
+
+    async_println!("{item_effect}").await.map_err(Into::into)
+}
+
+/// Creates a match-up over every type that takes part in match-ups, requesting each type concurrently.
+async fn type_matchup(client: &RustemonClient) -> Result<TypeMatchup<'_>> {
+    let types = rustemon::pokemon::type_::get_all_entries(client).await?;
+    let mut types = utility::follow_all(client, &types).await?;
+
+    types.retain(pokesearch::matchup::is_matchup_type);
+
+    TypeMatchup::from_types(client, &types, utility::language_prefs().clone())
+}
+
+async fn run_type(arguments: &Arguments, client: RustemonClient, api_text: &str) -> Result<()> {
+    let mut types = Vec::new();
+    let mut matchup = self::type_matchup(&client).await?;
+
+    matchup.set_threshold(arguments.threshold);
+    matchup.set_band(arguments.min_multiplier, arguments.max_multiplier);
+    matchup.set_pretty(arguments.pretty);
+    matchup.set_explain(arguments.explain);
+    matchup.set_compact(arguments.compact_matchup);
+    matchup.set_max_rows(arguments.max_matchups.map(NonZeroUsize::get));
+    matchup.set_glyphs(arguments.glyphs());
+
+    for type_ in api_text.split(',').map(|v| v.trim_matches('-')).filter(|v| !v.is_empty()) {
+        if !types.contains(&type_) {
+            types.push(type_);
+        }
+    }
+
+    let mut type_names = Vec::with_capacity(types.len());
+
+    for type_ in &types {
+        let type_ = self::search("type", type_, type_, rustemon::pokemon::type_::get_by_name(type_, &client)).await?;
+
+        if arguments.show_id {
+            type_names.push(self::with_id(arguments, &localized_search(&type_.names)?.name, type_.id));
+        }
+
+        if arguments.offensive {
+            matchup.apply_offensive_relations(&type_, false)?;
+        } else {
+            matchup.apply_type(&type_, Direction::Defensive)?;
+        }
+    }
+
+    match arguments.format {
+        OutputFormat::Csv => async_print!("{}", matchup.to_csv()).await.map_err(Into::into),
+        OutputFormat::Tsv => async_print!("{}", matchup.to_tsv()).await.map_err(Into::into),
+        OutputFormat::Json => async_print!("{}", matchup.to_json()?).await.map_err(Into::into),
+        OutputFormat::Jsonl => async_print!("{}", matchup.to_jsonl()).await.map_err(Into::into),
+        OutputFormat::Yaml => async_print!("{}", matchup.to_yaml()?).await.map_err(Into::into),
+        OutputFormat::Markdown => async_print!("{}", matchup.to_markdown()).await.map_err(Into::into),
+        OutputFormat::Html => async_print!("{}", matchup.to_html()).await.map_err(Into::into),
+        OutputFormat::Text if type_names.is_empty() => async_print!("{}", matchup.to_text()).await.map_err(Into::into),
+        OutputFormat::Text => {
+            async_println!("{}\n", type_names.join(", ")).await?;
+
+            async_print!("{}", matchup.to_text()).await.map_err(Into::into)
+        }
+    }
+}
+
+async fn run_pokedex(arguments: &Arguments, client: RustemonClient, api_text: &str) -> Result<()> {
+    let pokedex =
+        self::search("pokedex", &arguments.text, api_text, rustemon::games::pokedex::get_by_name(api_text, &client))
+            .await?;
+
+    if arguments.count {
+        return async_println!("{}", pokedex.pokemon_entries.len()).await.map_err(Into::into);
+    }
+
+    let pokedex_name = &localized_search(&pokedex.names)?.name;
+
+    if let Some(region) = &pokedex.region {
+        let pokedex_region = localized_search(&region.follow(&client).await?.names)?.name.to_owned();
+
+        async_println!("{pokedex_name} ({pokedex_region})\n").await?;
+    } else {
+        async_println!("{pokedex_name}\n").await?;
+    }
+
+    let mut entries = pokedex.pokemon_entries.iter().collect::<Vec<_>>();
+
+    entries.sort_unstable_by_key(|v| v.entry_number);
+
+    let (entries, footer) = self::page(arguments, &entries)?;
+
+    for entry in entries {
+        let species_name = localized_search(&entry.pokemon_species.follow(&client).await?.names)?.name.to_owned();
+
+        async_println!("#{}\t{species_name}", entry.entry_number).await?;
+    }
+
+    self::print_page_footer(footer).await
+}
+
+async fn run_growth_rate(arguments: &Arguments, client: RustemonClient, api_text: &str) -> Result<()> {
+    let growth_rate = self::search(
+        "growth rate",
+        &arguments.text,
+        api_text,
+        rustemon::pokemon::growth_rate::get_by_name(api_text, &client),
+    )
+    .await?;
+
+    if arguments.count {
+        return async_println!("{}", growth_rate.pokemon_species.len()).await.map_err(Into::into);
+    }
+
+    let growth_rate_name = &localized_search_by(&growth_rate.descriptions, |v| &v.language)?.description;
+
+    async_println!("{growth_rate_name}\n\nFormula:\t{}\n", growth_rate.formula).await?;
+
+    let mut levels = growth_rate.levels.iter().filter(|v| v.level % 10 == 0).collect::<Vec<_>>();
+
+    levels.sort_unstable_by_key(|v| v.level);
+
+    for level in levels {
+        async_println!("Lv. {}\t{} EXP", level.level, level.experience).await?;
+    }
+
+    async_println!("\n---\n").await?;
+
+    let mut species = growth_rate.pokemon_species;
+
+    if let Some(sort_by) = arguments.sort_by() {
+        utility::sort_resources(&mut species, sort_by);
+    }
+
+    let (species, footer) = self::page(arguments, &species)?;
+
+    for species in species {
+        let species_name = localized_search(&species.follow(&client).await?.names)?.name.to_owned();
+
+        async_println!("{species_name}").await?;
+    }
+
+    self::print_page_footer(footer).await
+}
+
+async fn characteristic(arguments: &Arguments, client: &RustemonClient, api_text: &str) -> Result<Characteristic> {
+    if let Ok(id) = api_text.parse() {
+        return self::search(
+            "characteristic",
+            &arguments.text,
+            id,
+            rustemon::pokemon::characteristic::get_by_id(id, client),
+        )
+        .await;
+    }
+
+    let (stat, gene_modulo) = self::characteristic_query(&arguments.text, api_text)?;
+    let stat = self::search("stat", stat, stat, rustemon::pokemon::stat::get_by_name(stat, client)).await?;
+
+    for characteristic in &stat.characteristics {
+        let characteristic = characteristic.follow(client).await?;
+
+        if characteristic.gene_modulo == gene_modulo {
+            return Ok(characteristic);
+        }
+    }
+
+    bail!(error::Error::not_found(format!(
+        "failed to resolve characteristic '{}' - no characteristic has an iv mod of {gene_modulo}",
+        arguments.text
+    )))
+}
+
+/// Splits a characteristic query into its stat's API name and its iv mod, which must be between 0 and 4.
+///
+/// Spaces within the query are replaced with dashes, so both halves are stripped of them as well as of whitespace.
+fn characteristic_query<'t>(text: &str, api_text: &'t str) -> Result<(&'t str, i64)> {
+    let Some((stat, gene_modulo)) = api_text.split_once(',') else {
+        bail!(error::Error::invalid_arguments(format!(
+            "expected a characteristic id or a '<stat>,<iv mod>' pair, found '{text}'"
+        )));
+    };
+    let (stat, gene_modulo) = (stat.trim().trim_matches('-'), gene_modulo.trim().trim_matches('-'));
+
+    match gene_modulo.parse() {
+        Ok(gene_modulo @ 0 ..= 4) => Ok((stat, gene_modulo)),
+        _ => {
+            bail!(error::Error::invalid_arguments(format!("expected an iv mod between 0 and 4, found '{gene_modulo}'")))
+        }
+    }
+}
+
+async fn run_characteristic(arguments: &Arguments, client: RustemonClient, api_text: &str) -> Result<()> {
+    let characteristic = self::characteristic(arguments, &client, api_text).await?;
+
+    let characteristic_name = &localized_search_by(&characteristic.descriptions, |v| &v.language)?.description;
+    let characteristic_stat =
+        localized_search(&characteristic.highest_stat.follow(&client).await?.names)?.name.to_owned();
+    let characteristic_values = characteristic.possible_values.iter().map(i64::to_string).collect::<Vec<_>>();
+
+    async_println!("{characteristic_name}\n").await?;
+    async_println!("Highest Stat:\t{characteristic_stat}").await?;
+    async_println!("IV Mod:\t\t{}", characteristic.gene_modulo).await?;
+    async_println!("Possible IVs:\t{}", characteristic_values.join(", ")).await.map_err(Into::into)
+}
+
+async fn run_damage_class(arguments: &Arguments, client: RustemonClient, api_text: &str) -> Result<()> {
+    let damage_class = self::search(
+        "damage class",
+        &arguments.text,
+        api_text,
+        rustemon::moves::move_damage_class::get_by_name(api_text, &client),
+    )
+    .await?;
+    let mut damage_class_moves = damage_class.moves;
+
+    self::retain_power(arguments, &client, &mut damage_class_moves).await?;
+
+    if arguments.count {
+        return async_println!("{}", damage_class_moves.len()).await.map_err(Into::into);
+    }
+
+    let damage_class_name = &localized_search(&damage_class.names)?.name;
+    let damage_class_description = &localized_search_by(&damage_class.descriptions, |v| &v.language)?.description;
+
+    async_println!("{damage_class_name}\n\n---\n\n{damage_class_description}\n").await?;
+
+    if let Some(sort_by) = arguments.sort_by() {
+        utility::sort_resources(&mut damage_class_moves, sort_by);
+    }
+
+    let (moves, footer) = self::page(arguments, &damage_class_moves)?;
+
+    for move_ in moves {
+        let move_name = localized_search(&move_.follow(&client).await?.names)?.name.to_owned();
+
+        async_println!("{move_name}").await?;
+    }
+
+    self::print_page_footer(footer).await
+}
+
+async fn run_generation(arguments: &Arguments, client: RustemonClient, _: &str) -> Result<()> {
+    let generation = self::generation(&client, &arguments.text).await?;
+
+    if arguments.count {
+        return async_println!("{}", generation.pokemon_species.len()).await.map_err(Into::into);
+    }
+
+    let generation_name = &localized_search(&generation.names)?.name;
+    let generation_region = localized_search(&generation.main_region.follow(&client).await?.names)?.name.to_owned();
+
+    async_println!("{generation_name} ({generation_region})\n").await?;
+
+    let mut version_group_names = Vec::with_capacity(generation.version_groups.len());
+
+    for version_group in &generation.version_groups {
+        version_group_names.push(self::version_group_name(&client, &version_group.follow(&client).await?).await?);
+    }
+
+    async_println!("Games:\t\t{}", version_group_names.join(", ")).await?;
+    async_println!("Species:\t{}", generation.pokemon_species.len()).await?;
+    async_println!("Moves:\t\t{}", generation.moves.len()).await?;
+    async_println!("Abilities:\t{}", generation.abilities.len()).await?;
+    async_println!("Types:\t\t{}\n\n---\n", generation.types.len()).await?;
+
+    let mut species = generation.pokemon_species.iter().collect::<Vec<_>>();
+
+    species.sort_unstable_by_key(|v| lookup::resource_id(&v.url));
+
+    let (species, footer) = self::page(arguments, &species)?;
+
+    for species in species {
+        let species_name = localized_search(&species.follow(&client).await?.names)?.name.to_owned();
+
+        async_println!("{species_name}").await?;
+    }
+
+    self::print_page_footer(footer).await
+}
+
+async fn run_breed(arguments: &Arguments, client: RustemonClient, api_text: &str) -> Result<()> {
+    let Some((first, second)) = api_text.split_once(',').map(|(a, b)| (a.trim_matches('-'), b.trim_matches('-')))
+    else {
+        bail!(error::Error::invalid_arguments(format!(
+            "expected two comma-separated pokemon, found '{}'",
+            arguments.text
+        )));
+    };
+
+    let mut species = Vec::with_capacity(2);
+
+    for text in [first, second] {
+        let pokemon =
+            self::search("pokemon", text, text, rustemon::pokemon::pokemon::get_by_name(text, &client)).await?;
+
+        species.push(pokemon.species.follow(&client).await?);
+    }
+
+    let first_name = &localized_search(&species[0].names)?.name;
+    let second_name = &localized_search(&species[1].names)?.name;
+
+    let is_group = |species: &PokemonSpecies, group: &str| species.egg_groups.iter().any(|v| v.name == group);
+    let is_ditto = |species: &PokemonSpecies| is_group(species, "ditto");
+
+    let reason = if is_group(&species[0], "no-eggs") || is_group(&species[1], "no-eggs") {
+        Some("one of them cannot breed at all")
+    } else if is_ditto(&species[0]) && is_ditto(&species[1]) {
+        Some("two Ditto cannot breed together")
+    } else if is_ditto(&species[0]) || is_ditto(&species[1]) {
+        None
+    } else if species[0].gender_rate < 0 || species[1].gender_rate < 0 {
+        Some("genderless Pokémon can only breed with Ditto")
+    } else if species[0].gender_rate == species[1].gender_rate && matches!(species[0].gender_rate, 0 | 8) {
+        Some("they cannot be of opposite genders")
+    } else if !species[0].egg_groups.iter().any(|v| is_group(&species[1], &v.name)) {
+        Some("they share no egg group")
+    } else {
+        None
+    };
+
+    if let Some(reason) = reason {
+        let reason = arguments.glyphs().fold(reason);
+
+        return async_println!("{first_name} and {second_name} cannot breed - {reason}").await.map_err(Into::into);
+    }
+
+    let mut group_names = Vec::new();
+
+    for group in &species[0].egg_groups {
+        if is_ditto(&species[0]) || is_ditto(&species[1]) || is_group(&species[1], &group.name) {
+            group_names.push(localized_search(&group.follow(&client).await?.names)?.name.to_owned());
+        }
+    }
+
+    async_println!("{first_name} and {second_name} can breed ({})", group_names.join(", ")).await.map_err(Into::into)
+}
+
+async fn run_machine(arguments: &Arguments, client: RustemonClient, api_text: &str) -> Result<()> {
+    let Ok(id) = api_text.parse() else {
+        bail!(error::Error::invalid_arguments(format!("expected a numeric machine id, found '{}'", arguments.text)));
+    };
+
+    let machine =
+        self::search("machine", &arguments.text, id, rustemon::machines::machine::get_by_id(id, &client)).await?;
+
+    let machine_name = localized_search(&machine.item.follow(&client).await?.names)?.name.to_owned();
+    let machine_games = self::version_group_name(&client, &machine.version_group.follow(&client).await?).await?;
+    let machine_move = localized_search(&machine.move_.follow(&client).await?.names)?.name.to_owned();
+
+    async_println!("{machine_name} ({machine_games})\n\nTeaches:\t{machine_move}").await.map_err(Into::into)
+}
+
+async fn run_list(arguments: &Arguments, client: RustemonClient, api_text: &str) -> Result<()> {
+    const LIST_KINDS: &[&str] = &[
+        "ability",
+        "damage-class",
+        "egg-group",
+        "generation",
+        "growth-rate",
+        "item",
+        "item-category",
+        "move",
+        "nature",
+        "pokedex",
+        "pokemon",
+        "species",
+        "type",
+        "version",
+        "version-group",
+    ];
+
+    let mut entries = match api_text {
+        "ability" => utility::resource_entries(rustemon::pokemon::ability::get_all_entries(&client).await?),
+        "damage-class" => {
+            utility::resource_entries(rustemon::moves::move_damage_class::get_all_entries(&client).await?)
+        }
+        "egg-group" => utility::resource_entries(rustemon::pokemon::egg_group::get_all_entries(&client).await?),
+        "generation" => utility::resource_entries(rustemon::games::generation::get_all_entries(&client).await?),
+        "growth-rate" => utility::resource_entries(rustemon::pokemon::growth_rate::get_all_entries(&client).await?),
+        "item" => utility::resource_entries(rustemon::items::item::get_all_entries(&client).await?),
+        "item-category" => utility::resource_entries(rustemon::items::item_category::get_all_entries(&client).await?),
+        "move" => utility::resource_entries(rustemon::moves::move_::get_all_entries(&client).await?),
+        "nature" => utility::resource_entries(rustemon::pokemon::nature::get_all_entries(&client).await?),
+        "pokedex" => utility::resource_entries(rustemon::games::pokedex::get_all_entries(&client).await?),
+        // Every species is listed once, regardless of how many forms it has.
+        "pokemon" if arguments.dedupe_forms => {
+            utility::resource_entries(rustemon::pokemon::pokemon_species::get_all_entries(&client).await?)
+        }
+        "pokemon" => utility::resource_entries(rustemon::pokemon::pokemon::get_all_entries(&client).await?),
+        "species" => utility::resource_entries(rustemon::pokemon::pokemon_species::get_all_entries(&client).await?),
+        "type" => utility::resource_entries(rustemon::pokemon::type_::get_all_entries(&client).await?),
+        "version" => utility::resource_entries(rustemon::games::version::get_all_entries(&client).await?),
+        "version-group" => utility::resource_entries(rustemon::games::version_group::get_all_entries(&client).await?),
+        _ => bail!(error::Error::invalid_arguments(format!(
+            "unknown list kind '{}' - expected one of: {}",
+            arguments.text,
+            LIST_KINDS.join(", ")
+        ))),
+    };
+
+    if let Some(text) = arguments.type_filter.as_deref() {
+        if api_text != "pokemon" {
+            bail!(error::Error::invalid_arguments("only Pokémon may be listed by type"));
+        }
+
+        let type_api_text = text.replace(' ', "-").to_lowercase();
+        let type_ =
+            self::search("type", text, &type_api_text, rustemon::pokemon::type_::get_by_name(&type_api_text, &client))
+                .await?;
+        let type_pokemon = type_.pokemon.into_iter().map(|v| v.pokemon).collect::<Vec<_>>();
+
+        let names = if arguments.dedupe_forms {
+            let type_pokemon = utility::follow_all(&client, &type_pokemon).await?;
+
+            type_pokemon.into_iter().map(|v| v.species.name).collect::<HashSet<_>>()
+        } else {
+            type_pokemon.into_iter().map(|v| v.name).collect::<HashSet<_>>()
+        };
+
+        entries.retain(|(_, v)| names.contains(v));
+    }
+
+    if let Some(filter) = arguments.filter.as_deref() {
+        let filter = filter.replace(' ', "-").to_lowercase();
+
+        entries.retain(|(_, v)| v.contains(&filter));
+    }
+
+    if arguments.count {
+        return async_println!("{}", entries.len()).await.map_err(Into::into);
+    }
+
+    if arguments.sort_by() == Some(SortBy::Id) {
+        entries.sort_unstable();
+    } else {
+        entries.sort_unstable_by(|(_, a), (_, b)| a.cmp(b));
+    }
+
+    if arguments.group_by_generation {
+        return self::print_list_by_generation(arguments, &client, api_text, &entries).await;
+    }
+
+    let (entries, footer) = self::page(arguments, &entries)?;
+
+    if arguments.format == OutputFormat::Tsv {
+        async_println!("id\tname").await?;
+
+        for (id, name) in entries {
+            let id = id.map_or_else(|| "-".to_owned(), |v| v.to_string());
+
+            async_println!("{id}\t{}", format::tsv_field(name)).await?;
+        }
+
+        return Ok(());
+    }
+
+    for (id, name) in entries {
+        match id.filter(|_| arguments.by_dex) {
+            Some(id) => async_println!("#{id}\t{name}").await?,
+            None => async_println!("{name}").await?,
+        }
+    }
+
+    self::print_page_footer(footer).await
+}
+
+async fn print_list_by_generation(
+    arguments: &Arguments,
+    client: &RustemonClient,
+    api_text: &str,
+    entries: &[(Option<i64>, String)],
+) -> Result<()> {
+    let names = entries.iter().map(|(_, v)| v.as_str()).collect::<HashSet<_>>();
+
+    let generations = match api_text {
+        "ability" => {
+            let mut resources = rustemon::pokemon::ability::get_all_entries(client).await?;
+
+            resources.retain(|v| names.contains(v.name.as_str()));
+
+            let abilities = utility::follow_all(client, &resources).await?;
+
+            resources.into_iter().zip(abilities).map(|(r, v)| (r.name, v.generation)).collect::<HashMap<_, _>>()
+        }
+        "move" => {
+            let mut resources = rustemon::moves::move_::get_all_entries(client).await?;
+
+            resources.retain(|v| names.contains(v.name.as_str()));
+
+            let moves = utility::follow_all(client, &resources).await?;
+
+            resources.into_iter().zip(moves).map(|(r, v)| (r.name, v.generation)).collect::<HashMap<_, _>>()
+        }
+        _ => bail!(error::Error::invalid_arguments("only abilities and moves may be grouped by generation")),
+    };
+
+    let mut entries = entries.iter().filter_map(|v| Some((generations.get(&v.1)?, v))).collect::<Vec<_>>();
+
+    // The sort is stable, so entries keep their existing order within each generation.
+    entries.sort_by_key(|(generation, _)| lookup::resource_id(&generation.url));
+
+    let (entries, footer) = self::page(arguments, &entries)?;
+
+    if arguments.format == OutputFormat::Tsv {
+        async_println!("generation\tid\tname").await?;
+
+        for (generation, (id, name)) in entries {
+            let id = id.map_or_else(|| "-".to_owned(), |v| v.to_string());
+
+            async_println!("{}\t{id}\t{}", generation.name, format::tsv_field(name)).await?;
+        }
+
+        return Ok(());
+    }
+
+    let mut current_generation = None;
+
+    for (generation, (id, name)) in entries {
+        if current_generation != Some(&generation.name) {
+            let generation_name = localized_search(&generation.follow(client).await?.names)?.name.to_owned();
+            let separator = if current_generation.is_some() { "\n" } else { "" };
+
+            async_println!("{separator}{generation_name}:").await?;
+
+            current_generation = Some(&generation.name);
+        }
+
+        match id.filter(|_| arguments.by_dex) {
+            Some(id) => async_println!("#{id}\t{name}").await?,
+            None => async_println!("{name}").await?,
+        }
+    }
+
+    self::print_page_footer(footer).await
+}
+
+async fn run_item_category(arguments: &Arguments, client: RustemonClient, api_text: &str) -> Result<()> {
+    let item_category = self::search(
+        "item category",
+        &arguments.text,
+        api_text,
+        rustemon::items::item_category::get_by_name(api_text, &client),
+    )
+    .await?;
+
+    if arguments.count {
+        return async_println!("{}", item_category.items.len()).await.map_err(Into::into);
+    }
+
+    let item_category_name = &localized_search(&item_category.names)?.name;
+    let item_category_pocket = localized_search(&item_category.pocket.follow(&client).await?.names)?.name.to_owned();
+
+    async_println!("{item_category_name} ({item_category_pocket} pocket)\n\n---\n").await?;
+
+    let mut items = item_category.items;
+
+    if let Some(sort_by) = arguments.sort_by() {
+        utility::sort_resources(&mut items, sort_by);
+    }
+
+    let (items, footer) = self::page(arguments, &items)?;
+
+    for item in items {
+        let item_name = localized_search(&item.follow(&client).await?.names)?.name.to_owned();
+
+        async_println!("{item_name}").await?;
+    }
+
+    self::print_page_footer(footer).await
+}
+
+async fn run_top(arguments: &Arguments, client: RustemonClient, api_text: &str) -> Result<()> {
+    let stat = self::search("stat", &arguments.text, api_text, rustemon::pokemon::stat::get_by_name(api_text, &client))
+        .await?;
+    let stat_name = &localized_search(&stat.names)?.name;
+
+    let entries = rustemon::pokemon::pokemon::get_all_entries(&client).await?;
+    let mut pokemon = utility::follow_all(&client, &entries)
+        .await?
+        .into_iter()
+        .filter_map(|v| Some((v.stats.iter().find(|s| s.stat.name == stat.name)?.base_stat, v)))
+        .collect::<Vec<_>>();
+
+    if arguments.ascending {
+        pokemon.sort_by_key(|(value, v)| (*value, v.id));
+    } else {
+        pokemon.sort_by_key(|(value, v)| (Reverse(*value), v.id));
+    }
+
+    async_println!("{} {stat_name}\n\n---\n", arguments.glyphs().fold("Pokémon by")).await?;
+
+    for (rank, (value, pokemon)) in pokemon.into_iter().take(arguments.limit.unwrap_or(10)).enumerate() {
+        let species_name = localized_search(&pokemon.species.follow(&client).await?.names)?.name.to_owned();
+        let pokemon_name = self::form_name(&client, &pokemon, &species_name).await?;
+
+        async_println!("#{}\t{value}\t{pokemon_name}", rank + 1).await?;
+    }
+
+    Ok(())
+}
+
+async fn run_team_coverage(arguments: &Arguments, client: RustemonClient, api_text: &str) -> Result<()> {
+    let mut members = Vec::new();
+
+    for member in api_text.split(',').map(|v| v.trim_matches('-')).filter(|v| !v.is_empty()) {
+        if !members.contains(&member) {
+            members.push(member);
+        }
+    }
+
+    if members.len() < 2 {
+        bail!(error::Error::invalid_arguments(format!(
+            "expected at least two comma-separated pokemon, found '{}'",
+            arguments.text
+        )));
+    }
+
+    let mut matchup = self::type_matchup(&client).await?;
+    let mut member_names = Vec::with_capacity(members.len());
+    let mut threats = BTreeMap::<Arc<str>, Vec<usize>>::new();
+
+    for member in members {
+        let pokemon =
+            self::search("pokemon", member, member, rustemon::pokemon::pokemon::get_by_name(member, &client)).await?;
+        let species_name = localized_search(&pokemon.species.follow(&client).await?.names)?.name.to_owned();
+
+        matchup.reset();
+
+        let types =
+            utility::follow_all(&client, &pokemon.types.iter().map(|v| v.type_.clone()).collect::<Vec<_>>()).await?;
+
+        matchup.apply_pokemon_types(&types, Direction::Defensive)?;
+
+        for (_, type_list) in matchup.get().filter(|(multiplier, _)| *multiplier > 1.0) {
+            for type_name in type_list {
+                threats.entry(Arc::clone(type_name)).or_default().push(member_names.len());
+            }
+        }
+
+        member_names.push(self::form_name(&client, &pokemon, &species_name).await?);
+    }
+
+    let mut threats = threats.into_iter().filter(|(_, v)| v.len() > 1).collect::<Vec<_>>();
+
+    threats.sort_by_key(|(_, v)| Reverse(v.len()));
+
+    async_println!("Team:\t\t{}\n\n---\n", member_names.join(", ")).await?;
+
+    if threats.is_empty() {
+        return async_println!("No shared weaknesses").await.map_err(Into::into);
+    }
+
+    async_println!("Team threats:").await?;
+
+    for (type_name, indices) in threats {
+        let names = indices.iter().map(|v| &*member_names[*v]).collect::<Vec<_>>();
+
+        async_println!("{type_name}\t{}/{}\t{}", names.len(), member_names.len(), names.join(", ")).await?;
+    }
+
+    Ok(())
+}
+
+async fn run_move_by_effect(arguments: &Arguments, client: RustemonClient, api_text: &str) -> Result<()> {
+    let ailment = self::search(
+        "ailment",
+        &arguments.text,
+        api_text,
+        rustemon::moves::move_ailment::get_by_name(api_text, &client),
+    )
+    .await?;
+    let mut ailment_moves = ailment.moves;
+
+    self::retain_power(arguments, &client, &mut ailment_moves).await?;
+
+    if arguments.count {
+        return async_println!("{}", ailment_moves.len()).await.map_err(Into::into);
+    }
+
+    let ailment_name = &localized_search(&ailment.names)?.name;
+
+    // Moves that inflict the ailment as their primary effect report a chance of zero.
+    let mut moves = utility::follow_all(&client, &ailment_moves)
+        .await?
+        .into_iter()
+        .map(|v| (v.meta.as_ref().map_or(0, |v| v.ailment_chance), v))
+        .map(|(chance, v)| (if chance == 0 { 100 } else { chance }, v))
+        .collect::<Vec<_>>();
+
+    moves.sort_by_key(|(chance, v)| (Reverse(*chance), v.id));
+
+    async_println!("Moves inflicting {ailment_name}\n\n---\n").await?;
+
+    let (moves, footer) = self::page(arguments, &moves)?;
+
+    for (chance, move_) in moves {
+        let move_name = &localized_search(&move_.names)?.name;
+
+        async_println!("{chance}%\t{move_name}").await?;
+    }
+
+    self::print_page_footer(footer).await
+}
+
+async fn run_nature_chart(_: &Arguments, client: RustemonClient, _: &str) -> Result<()> {
+    // Natures only affect the five stats following HP, in order of their identifiers.
+    const STAT_IDS: std::ops::RangeInclusive<i64> = 2 ..= 6;
+
+    let mut stat_names = Vec::with_capacity(5);
+
+    for id in STAT_IDS {
+        stat_names
+            .push(localized_search(&rustemon::pokemon::stat::get_by_id(id, &client).await?.names)?.name.to_owned());
+    }
+
+    let entries = rustemon::pokemon::nature::get_all_entries(&client).await?;
+    let mut natures = utility::follow_all(&client, &entries).await?;
+    let mut grid = vec![vec![None::<String>; 5]; 5];
+
+    natures.sort_unstable_by_key(|v| v.id);
+
+    let stat_index = |stat: &Option<NamedApiResource<Stat>>| {
+        let id = lookup::resource_id(&stat.as_ref()?.url)?;
+
+        STAT_IDS.contains(&id).then(|| (id - STAT_IDS.start()) as usize)
+    };
+
+    // Neutral natures raise and lower the same stat, placing them along the diagonal.
+    for nature in &natures {
+        if let (Some(row), Some(column)) = (stat_index(&nature.increased_stat), stat_index(&nature.decreased_stat)) {
+            grid[row][column] = Some(localized_search(&nature.names)?.name.to_owned());
+        }
+    }
+
+    let cells = grid.iter().flatten().flatten().map(|v| v.chars().count());
+    let width = stat_names.iter().map(|v| v.chars().count() + 1).chain(cells).max().unwrap_or_default() + 2;
+    let mut header = format!("{:width$}", "");
+
+    for stat_name in &stat_names {
+        header.push_str(&format!("{:width$}", format!("-{stat_name}")));
+    }
+
+    let mut lines = vec![header.trim_end().to_owned()];
+
+    for (stat_name, row) in stat_names.iter().zip(&grid) {
+        let mut line = format!("{:width$}", format!("+{stat_name}"));
+
+        for cell in row {
+            line.push_str(&format!("{:width$}", cell.as_deref().unwrap_or("-")));
+        }
+
+        lines.push(line.trim_end().to_owned());
+    }
+
+    async_println!("{}", lines.join("\n")).await.map_err(Into::into)
+}
+
+async fn run_game(arguments: &Arguments, client: RustemonClient, api_text: &str) -> Result<()> {
+    let version =
+        self::search("game", &arguments.text, api_text, rustemon::games::version::get_by_name(api_text, &client))
+            .await?;
+    let version_name = &localized_search(&version.names)?.name;
+    let version_group = version.version_group.follow(&client).await?;
+    let version_generation = localized_search(&version_group.generation.follow(&client).await?.names)?.name.to_owned();
+
+    let mut other_names = Vec::with_capacity(version_group.versions.len());
+
+    for other in version_group.versions.iter().filter(|v| v.name != version.name) {
+        other_names.push(localized_search(&other.follow(&client).await?.names)?.name.to_owned());
+    }
+
+    async_println!("{version_name} ({version_generation})\n").await?;
+    async_println!("Version Group:\t{}", version_group.name).await?;
+
+    if other_names.is_empty() {
+        async_println!("Other Versions:\t-").await.map_err(Into::into)
+    } else {
+        async_println!("Other Versions:\t{}", other_names.join(", ")).await.map_err(Into::into)
+    }
+}
+
+async fn run_pokeathlon_stat(arguments: &Arguments, client: RustemonClient, api_text: &str) -> Result<()> {
+    let pokeathlon_stat = self::search(
+        "pokeathlon stat",
+        &arguments.text,
+        api_text,
+        rustemon::pokemon::pokeathlon_stat::get_by_name(api_text, &client),
+    )
+    .await?;
+    let pokeathlon_stat_name = &localized_search(&pokeathlon_stat.names)?.name;
+
+    async_println!("{pokeathlon_stat_name}\n").await?;
+
+    let affecting_natures = &pokeathlon_stat.affecting_natures;
+
+    for (label, natures) in
+        [("Increased By:", &affecting_natures.increase), ("Decreased By:", &affecting_natures.decrease)]
+    {
+        let mut nature_names = Vec::with_capacity(natures.len());
+
+        for nature in natures {
+            let nature_name = localized_search(&nature.nature.follow(&client).await?.names)?.name.to_owned();
+
+            nature_names.push((nature.max_change, nature_name));
+        }
+
+        nature_names.sort_by_key(|(max_change, _)| Reverse(max_change.abs()));
+
+        if nature_names.is_empty() {
+            async_println!("{label}\t-").await?;
+        } else {
+            let nature_names = nature_names.iter().map(|(max_change, name)| format!("{name} ({max_change:+})"));
+
+            async_println!("{label}\t{}", nature_names.collect::<Vec<_>>().join(", ")).await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn run_contest_type(arguments: &Arguments, client: RustemonClient, api_text: &str) -> Result<()> {
+    let contest_type = self::search(
+        "contest type",
+        &arguments.text,
+        api_text,
+        rustemon::contests::contest_type::get_by_name(api_text, &client),
+    )
+    .await?;
+    // Contest names may omit their language, so the English entry is looked up manually.
+    let contest_type_entry = contest_type.names.iter().find(|v| v.language.as_ref().is_some_and(|v| v.name == "en"));
+    let contest_type_name = contest_type_entry.and_then(|v| v.name.as_deref()).unwrap_or(&contest_type.name);
+    let contest_type_color = contest_type_entry.and_then(|v| v.color.as_deref()).unwrap_or("-");
+    let berry_flavor = localized_search(&contest_type.berry_flavor.follow(&client).await?.names)?.name.to_owned();
+
+    async_println!("{contest_type_name}\n").await?;
+    async_println!("Flavor:\t\t{berry_flavor}").await?;
+    async_println!("Color:\t\t{contest_type_color}").await.map_err(Into::into)
+}
+
+async fn run_cache_stats(arguments: &Arguments, _: RustemonClient, _: &str) -> Result<()> {
+    let stats = utility::cache_stats(&arguments.cache_dir);
+    let [oldest, newest] =
+        [stats.oldest, stats.newest].map(|v| v.map_or_else(|| "-".to_owned(), utility::format_timestamp));
+
+    async_println!("{}\n", arguments.cache_dir).await?;
+    async_println!("Entries:\t{}", stats.entries).await?;
+    async_println!("Size:\t\t{}", utility::format_size(stats.size)).await?;
+    async_println!("Oldest:\t\t{oldest}").await?;
+    async_println!("Newest:\t\t{newest}").await.map_err(Into::into)
+}
+
+async fn run_clear_cache(arguments: &Arguments, _: RustemonClient, _: &str) -> Result<()> {
+    let cache_dir = &*arguments.cache_dir;
+
+    if !arguments.yes {
+        async_print!("Clear every cached entry in '{cache_dir}'? [y/N] ").await?;
+        tokio::io::stdout().flush().await?;
+
+        let mut answer = String::new();
+
+        BufReader::new(tokio::io::stdin()).read_line(&mut answer).await?;
+
+        if !matches!(answer.trim().to_lowercase().as_str(), "y" | "yes") {
+            return async_println!("Left the cache unchanged").await.map_err(Into::into);
+        }
+    }
+
+    let entries = utility::cache_stats(cache_dir).entries;
+
+    cacache::clear_sync(cache_dir).with_context(|| format!("failed to clear the cache '{cache_dir}'"))?;
+
+    async_println!("Cleared {entries} cached entries").await.map_err(Into::into)
+}
+
+async fn run_move_set_overlap(arguments: &Arguments, client: RustemonClient, api_text: &str) -> Result<()> {
+    let members = api_text.split(',').map(|v| v.trim_matches('-')).filter(|v| !v.is_empty()).collect::<Vec<_>>();
+
+    let [first, second] = members[..] else {
+        bail!(error::Error::invalid_arguments(format!(
+            "expected exactly two comma-separated pokemon, found '{}'",
+            arguments.text
+        )));
+    };
+
+    let mut pokemon = Vec::with_capacity(2);
+
+    for member in [first, second] {
+        let member =
+            self::search("pokemon", member, member, rustemon::pokemon::pokemon::get_by_name(member, &client)).await?;
+        let species_name = localized_search(&member.species.follow(&client).await?.names)?.name.to_owned();
+
+        pokemon.push((species_name, member));
+    }
+
+    let unique_to = match arguments.unique_to.as_deref() {
+        Some(text) => match pokemon.iter().position(|(_, v)| v.name == text.replace(' ', "-").to_lowercase()) {
+            Some(index) => Some(index),
+            None => bail!(error::Error::invalid_arguments(format!(
+                "expected --unique-to to name one of the compared pokemon, found '{text}'"
+            ))),
+        },
+        None => None,
+    };
+
+    let [first_moves, second_moves] = [&pokemon[0].1, &pokemon[1].1]
+        .map(|v| v.moves.iter().map(|v| (&v.move_.name, &v.move_)).collect::<BTreeMap<_, _>>());
+
+    let (header, mut moves) = match unique_to {
+        Some(index) => {
+            let (own, other) = if index == 0 { (&first_moves, &second_moves) } else { (&second_moves, &first_moves) };
+            let moves = own.iter().filter(|(name, _)| !other.contains_key(*name)).map(|(_, v)| (*v).clone());
+
+            (format!("Moves only {} learns", pokemon[index].0), moves.collect::<Vec<_>>())
+        }
+        None => {
+            let moves =
+                first_moves.iter().filter(|(name, _)| second_moves.contains_key(*name)).map(|(_, v)| (*v).clone());
+
+            (format!("Moves shared by {} and {}", pokemon[0].0, pokemon[1].0), moves.collect::<Vec<_>>())
+        }
+    };
+
+    if arguments.count {
+        return async_println!("{}", moves.len()).await.map_err(Into::into);
+    }
+
+    if let Some(sort_by) = arguments.sort_by() {
+        utility::sort_resources(&mut moves, sort_by);
+    }
+
+    async_println!("{header}\n\n---\n").await?;
+
+    let (moves, footer) = self::page(arguments, &moves)?;
+
+    for move_ in utility::follow_all(&client, moves).await? {
+        let move_name = &localized_search(&move_.names)?.name;
+
+        async_println!("{move_name}").await?;
+    }
+
+    self::print_page_footer(footer).await
+}
+
+async fn run_evolution(arguments: &Arguments, client: RustemonClient, api_text: &str) -> Result<()> {
+    let species = self::search(
+        "species",
+        &arguments.text,
+        api_text,
+        rustemon::pokemon::pokemon_species::get_by_name(api_text, &client),
+    )
+    .await?;
+    let species_name = &localized_search(&species.names)?.name;
+
+    let Some(evolution_chain) = &species.evolution_chain else {
+        return async_println!("{species_name} does not evolve").await.map_err(Into::into);
+    };
+
+    let evolution_chain = evolution_chain.follow(&client).await?;
+
+    for (depth, link) in utility::flatten_chain(&evolution_chain.chain) {
+        let link_name = localized_search(&link.species.follow(&client).await?.names)?.name.to_owned();
+        let indent = "  ".repeat(depth);
+
+        if link.evolution_details.is_empty() {
+            async_println!("{indent}{link_name}").await?;
+        } else {
+            let details = link.evolution_details.iter().map(utility::format_evolution_detail);
+
+            async_println!("{indent}{link_name} ({})", details.collect::<Vec<_>>().join("; ")).await?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use pokesearch::{Direction, TypeMatchup};
+    use rustemon::model::pokemon::{Pokemon, PokemonForm, PokemonFormType, PokemonType};
+
+    use crate::arguments::{OutputFormat, SearchKind};
+    use crate::utility::tests::{self as fixtures, type_fixture};
+
+    fn pokemon(types: &[&str]) -> Pokemon {
+        let types = fixtures::type_resources(types).into_iter().zip(1 ..);
+
+        Pokemon { types: types.map(|(type_, slot)| PokemonType { slot, type_ }).collect(), ..Pokemon::default() }
+    }
+
+    fn matchup<'cl>(client: &'cl rustemon::client::RustemonClient, types: &[PokemonType]) -> TypeMatchup<'cl> {
+        let types = types.iter().map(|v| type_fixture(&v.type_.name)).collect::<Vec<_>>();
+        let mut matchup = fixtures::matchup(client);
+
+        matchup.apply_pokemon_types(&types, Direction::Defensive).unwrap();
+
+        matchup
+    }
+
+    #[test]
+    fn form_matchup_differs_from_default() {
+        let client = fixtures::client();
+        let rotom = self::pokemon(&["electric", "ghost"]);
+        let rotom_wash = self::pokemon(&["electric", "water"]);
+
+        let mut default = self::matchup(&client, &super::form_types(&rotom, None));
+        let mut wash = self::matchup(&client, &super::form_types(&rotom_wash, None));
+
+        assert_eq!(fixtures::multiplier(&mut default, "normal"), 0.0);
+        assert_eq!(fixtures::multiplier(&mut wash, "normal"), 1.0);
+        assert_ne!(default.multipliers(), wash.multipliers());
+    }
+
+    #[test]
+    fn form_types_override_the_pokemon() {
+        let client = fixtures::client();
+        let charizard = self::pokemon(&["fire", "flying"]);
+        let types = fixtures::type_resources(&["dragon", "fire"]).into_iter().zip([2, 1]);
+        let mega_x = PokemonForm {
+            types: types.map(|(type_, slot)| PokemonFormType { slot, type_ }).collect(),
+            ..PokemonForm::default()
+        };
+
+        let form_types = super::form_types(&charizard, Some(&mega_x));
+        let mut default = self::matchup(&client, &super::form_types(&charizard, None));
+        let mut form = self::matchup(&client, &form_types);
+
+        assert_eq!(form_types.iter().map(|v| &*v.type_.name).collect::<Vec<_>>(), ["fire", "dragon"]);
+        assert_eq!(fixtures::multiplier(&mut default, "rock"), 4.0);
+        assert_eq!(fixtures::multiplier(&mut form, "rock"), 2.0);
+        assert_eq!(super::form_types(&charizard, Some(&PokemonForm::default())), charizard.types);
+    }
+
+    #[test]
+    fn characteristic_queries_ignore_separating_spaces() {
+        assert_eq!(super::characteristic_query("attack, 2", "attack,-2").unwrap(), ("attack", 2));
+        assert_eq!(super::characteristic_query("special attack,4", "special-attack,4").unwrap(), ("special-attack", 4));
+        assert!(super::characteristic_query("attack, 5", "attack,-5").is_err());
+        assert!(super::characteristic_query("attack", "attack").is_err());
+    }
+
+    #[test]
+    fn formats_are_limited_to_supporting_searches() {
+        assert!(SearchKind::Type.supports(OutputFormat::Jsonl));
+        assert!(SearchKind::List.supports(OutputFormat::Tsv));
+        assert!(!SearchKind::List.supports(OutputFormat::Json));
+        assert!(!SearchKind::Ability.supports(OutputFormat::Csv));
+        assert!(SearchKind::Ability.supports(OutputFormat::Html));
+    }
+
+    #[test]
+    fn format_arguments_are_found_before_parsing() {
+        let arguments = |v: &[&str]| v.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            super::format_argument(arguments(&["pokesearch", "--format", "jsonl", "nope"])),
+            Some(OutputFormat::Jsonl)
+        );
+        assert_eq!(
+            super::format_argument(arguments(&["pokesearch", "type", "--format=yaml"])),
+            Some(OutputFormat::Yaml)
+        );
+        assert_eq!(super::format_argument(arguments(&["pokesearch", "--format", "xml"])), None);
+        assert_eq!(super::format_argument(arguments(&["pokesearch", "type", "fire"])), None);
+    }
 }
@@ -0,0 +1,643 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use std::sync::Arc;
+
+use anyhow::{Result, anyhow};
+use rustemon::Follow;
+use rustemon::client::RustemonClient;
+use rustemon::model::pokemon::{Type, TypeRelations};
+use rustemon::model::resource::NamedApiResource;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::glyphs::Glyphs;
+use crate::language::LanguagePrefs;
+use crate::{format, lookup};
+
+/// A damage multiplier alongside every type that it applies to.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Multiplier {
+    /// The damage multiplier.
+    pub multiplier: f64,
+    /// The English names of the types that the multiplier applies to.
+    pub types: Vec<String>,
+}
+
+/// The direction in which type relations are applied to a [`TypeMatchup`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// Relations describe damage dealt to each type.
+    Offensive,
+    /// Relations describe damage received from each type.
+    Defensive,
+}
+
+/// A match-up multiplier factor, alongside the name of the type that caused it.
+type Factor = (f64, Option<Arc<str>>);
+
+/// The damage multipliers of every type, as modified by the type relations applied to it.
+#[derive(Clone, Debug)]
+pub struct TypeMatchup<'cl> {
+    inner: HashMap<i64, (Arc<str>, f64)>,
+    immune: HashSet<i64>,
+    cache: Vec<(f64, Vec<Arc<str>>)>,
+    sources: Vec<(Arc<str>, bool)>,
+    source: Option<Arc<str>>,
+    explanations: HashMap<Arc<str>, Vec<Factor>>,
+    threshold: Option<f64>,
+    band: (Option<f64>, Option<f64>),
+    max_rows: Option<usize>,
+    pretty: bool,
+    explain: bool,
+    compact: bool,
+    glyphs: Glyphs,
+    prefs: LanguagePrefs,
+    client: &'cl RustemonClient,
+}
+
+impl<'cl> TypeMatchup<'cl> {
+    /// Creates a match-up over every type that takes part in match-ups, named in the preferred languages.
+    pub async fn new(client: &'cl RustemonClient, prefs: LanguagePrefs) -> Result<Self> {
+        let mut types = Vec::new();
+
+        for type_ in rustemon::pokemon::type_::get_all_entries(client).await? {
+            types.push(type_.follow(client).await?);
+        }
+
+        types.retain(self::is_matchup_type);
+
+        Self::from_types(client, &types, prefs)
+    }
+
+    /// Creates a match-up over the given types without requesting any, named in the preferred languages.
+    pub fn from_types(client: &'cl RustemonClient, types: &[Type], prefs: LanguagePrefs) -> Result<Self> {
+        let mut this = Self::empty(client, prefs);
+
+        for type_ in types {
+            let type_name = this.type_name(type_)?;
+
+            this.inner.insert(type_.id, (type_name, 1.0));
+        }
+
+        Ok(this)
+    }
+
+    fn empty(client: &'cl RustemonClient, prefs: LanguagePrefs) -> Self {
+        Self {
+            inner: HashMap::new(),
+            immune: HashSet::new(),
+            cache: Vec::new(),
+            sources: Vec::new(),
+            source: None,
+            explanations: HashMap::new(),
+            threshold: None,
+            band: (None, None),
+            max_rows: None,
+            pretty: false,
+            explain: false,
+            compact: false,
+            glyphs: Glyphs::UNICODE,
+            prefs,
+            client,
+        }
+    }
+
+    fn type_name(&self, type_: &Type) -> Result<Arc<str>> {
+        Ok(self.prefs.search(&type_.names, |v| &v.language.name)?.name.as_str().into())
+    }
+
+    /// Restores every multiplier to ×1 and forgets all applied relations, keeping the fetched type names.
+    pub fn reset(&mut self) {
+        for (_, multiplier) in self.inner.values_mut() {
+            *multiplier = 1.0;
+        }
+
+        self.immune.clear();
+        self.cache.clear();
+        self.sources.clear();
+        self.explanations.clear();
+    }
+
+    pub fn set_threshold(&mut self, threshold: Option<f64>) {
+        self.threshold = threshold;
+    }
+
+    /// Sets the inclusive range of multipliers to display.
+    pub fn set_band(&mut self, min: Option<f64>, max: Option<f64>) {
+        self.band = (min, max);
+    }
+
+    /// Sets the maximum number of multipliers to display, keeping those furthest from ×1.
+    pub fn set_max_rows(&mut self, max_rows: Option<usize>) {
+        self.max_rows = max_rows;
+    }
+
+    pub fn type_names(&self, types: &[NamedApiResource<Type>]) -> Vec<Arc<str>> {
+        let mut names = types
+            .iter()
+            .filter_map(|v| lookup::resource_id(&v.url))
+            .filter_map(|v| self.inner.get(&v))
+            .map(|(name, _)| Arc::clone(name))
+            .collect::<Vec<_>>();
+
+        names.sort_unstable();
+
+        names
+    }
+
+    pub fn set_pretty(&mut self, pretty: bool) {
+        self.pretty = pretty;
+    }
+
+    pub fn set_explain(&mut self, explain: bool) {
+        self.explain = explain;
+    }
+
+    pub fn set_compact(&mut self, compact: bool) {
+        self.compact = compact;
+    }
+
+    pub fn set_glyphs(&mut self, glyphs: Glyphs) {
+        self.glyphs = glyphs;
+    }
+
+    fn modify_type(&mut self, id: i64, factor: f64) {
+        if !self.cache.is_empty() {
+            self.cache.clear();
+        }
+
+        if let Some((name, multiplier)) = self.inner.get_mut(&id) {
+            *multiplier *= factor;
+
+            self.explanations.entry(Arc::clone(name)).or_default().push((factor, self.source.clone()));
+        }
+    }
+
+    fn mark_immune(&mut self, id: i64) {
+        if !self.cache.is_empty() {
+            self.cache.clear();
+        }
+
+        if let Some((name, _)) = self.inner.get(&id) {
+            self.immune.insert(id);
+            self.explanations.entry(Arc::clone(name)).or_default().push((0.0, self.source.clone()));
+        }
+    }
+
+    /// Returns the identifier of the referenced type, which is read from its URL rather than requested.
+    fn type_id(type_: &NamedApiResource<Type>) -> Result<i64> {
+        lookup::resource_id(&type_.url).ok_or_else(|| anyhow!("invalid type URL '{}'", type_.url))
+    }
+
+    /// Applies the relations of the given type, recording it as the source of each resulting modification.
+    pub fn apply_type(&mut self, type_: &Type, direction: Direction) -> Result<()> {
+        self.source = Some(self.type_name(type_)?);
+
+        let result = self.apply_relations(&type_.damage_relations, direction);
+
+        self.source = None;
+
+        result
+    }
+
+    /// Applies every type of a Pokémon, treating each as a STAB source when offensive.
+    pub fn apply_pokemon_types(&mut self, types: &[Type], direction: Direction) -> Result<()> {
+        for type_ in types {
+            match direction {
+                Direction::Offensive => self.apply_offensive_relations(type_, true)?,
+                Direction::Defensive => self.apply_type(type_, Direction::Defensive)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn apply_relations(&mut self, relations: &TypeRelations, direction: Direction) -> Result<()> {
+        if direction == Direction::Offensive {
+            for type_ in &relations.no_damage_to {
+                self.no_damage_to_resource(type_)?;
+            }
+            for type_ in &relations.double_damage_to {
+                self.double_damage_to_resource(type_)?;
+            }
+            for type_ in &relations.half_damage_to {
+                self.half_damage_to_resource(type_)?;
+            }
+
+            return Ok(());
+        }
+
+        for type_ in &relations.no_damage_from {
+            self.no_damage_from_resource(type_)?;
+        }
+        for type_ in &relations.double_damage_from {
+            self.double_damage_from_resource(type_)?;
+        }
+        for type_ in &relations.half_damage_from {
+            self.half_damage_from_resource(type_)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn apply_offensive_relations(&mut self, type_: &Type, stab: bool) -> Result<()> {
+        let mut multipliers = self.inner.keys().map(|id| (*id, 1.0)).collect::<HashMap<_, f64>>();
+
+        for target in &type_.damage_relations.no_damage_to {
+            multipliers.entry(Self::type_id(target)?).and_modify(|v| *v = 0.0);
+        }
+        for target in &type_.damage_relations.double_damage_to {
+            multipliers.entry(Self::type_id(target)?).and_modify(|v| *v *= 2.0);
+        }
+        for target in &type_.damage_relations.half_damage_to {
+            multipliers.entry(Self::type_id(target)?).and_modify(|v| *v /= 2.0);
+        }
+
+        let is_first_source = self.sources.is_empty();
+        let source = self.type_name(type_)?;
+
+        for (id, (name, multiplier)) in &mut self.inner {
+            let source_multiplier = multipliers[id];
+
+            if is_first_source || source_multiplier > *multiplier {
+                *multiplier = source_multiplier;
+
+                self.explanations.insert(Arc::clone(name), vec![(source_multiplier, Some(Arc::clone(&source)))]);
+            }
+        }
+
+        self.cache.clear();
+        self.sources.push((source, stab));
+
+        Ok(())
+    }
+
+    pub fn no_damage_from(&mut self, type_: &Type) {
+        self.mark_immune(type_.id);
+    }
+
+    pub fn half_damage_from(&mut self, type_: &Type) {
+        self.modify_type(type_.id, 0.5);
+    }
+
+    pub fn double_damage_from(&mut self, type_: &Type) {
+        self.modify_type(type_.id, 2.0);
+    }
+
+    pub async fn no_damage_from_name(&mut self, type_: &str) -> Result<()> {
+        let type_ = rustemon::pokemon::type_::get_by_name(type_, self.client).await?;
+
+        self.mark_immune(type_.id);
+
+        Ok(())
+    }
+
+    pub async fn half_damage_from_name(&mut self, type_: &str) -> Result<()> {
+        let type_ = rustemon::pokemon::type_::get_by_name(type_, self.client).await?;
+
+        self.modify_type(type_.id, 0.5);
+
+        Ok(())
+    }
+
+    pub async fn double_damage_from_name(&mut self, type_: &str) -> Result<()> {
+        let type_ = rustemon::pokemon::type_::get_by_name(type_, self.client).await?;
+
+        self.modify_type(type_.id, 2.0);
+
+        Ok(())
+    }
+
+    pub fn no_damage_from_resource(&mut self, type_: &NamedApiResource<Type>) -> Result<()> {
+        self.mark_immune(Self::type_id(type_)?);
+
+        Ok(())
+    }
+
+    pub fn half_damage_from_resource(&mut self, type_: &NamedApiResource<Type>) -> Result<()> {
+        self.modify_type(Self::type_id(type_)?, 0.5);
+
+        Ok(())
+    }
+
+    pub fn double_damage_from_resource(&mut self, type_: &NamedApiResource<Type>) -> Result<()> {
+        self.modify_type(Self::type_id(type_)?, 2.0);
+
+        Ok(())
+    }
+
+    pub fn no_damage_to_resource(&mut self, type_: &NamedApiResource<Type>) -> Result<()> {
+        self.mark_immune(Self::type_id(type_)?);
+
+        Ok(())
+    }
+
+    pub fn half_damage_to_resource(&mut self, type_: &NamedApiResource<Type>) -> Result<()> {
+        self.modify_type(Self::type_id(type_)?, 0.5);
+
+        Ok(())
+    }
+
+    pub fn double_damage_to_resource(&mut self, type_: &NamedApiResource<Type>) -> Result<()> {
+        self.modify_type(Self::type_id(type_)?, 2.0);
+
+        Ok(())
+    }
+
+    /// Returns each multiplier above the threshold and within the band, alongside the types that it applies to.
+    pub fn multipliers(&mut self) -> Vec<Multiplier> {
+        self.get()
+            .map(|(multiplier, types)| Multiplier { multiplier, types: types.iter().map(|v| v.to_string()).collect() })
+            .collect()
+    }
+
+    pub fn get(&mut self) -> impl Iterator<Item = (f64, &[Arc<str>])> {
+        if self.cache.is_empty() {
+            self.cache = self
+                .inner
+                .iter()
+                .fold(HashMap::<u32, Vec<Arc<str>>>::new(), |mut map, (id, (name, mult))| {
+                    let mult = if self.immune.contains(id) { 0.0 } else { *mult };
+
+                    // Three or more types may combine into eighths, so the key must keep at least three decimals.
+                    map.entry((mult * 1000.0).round() as u32).or_default().push(Arc::clone(name));
+
+                    map
+                })
+                .into_iter()
+                .map(|(m, mut v)| {
+                    self::sort_names(&mut v);
+
+                    (m as f64 / 1000.0, v)
+                })
+                .collect::<Vec<_>>();
+
+            self.cache.sort_unstable_by_key(|(m, _)| (*m * 1000.0).round() as u32);
+            self.cache.reverse();
+        }
+
+        let threshold = self.threshold.unwrap_or(f64::NEG_INFINITY);
+        let min = self.band.0.unwrap_or(f64::NEG_INFINITY).max(threshold);
+        let max = self.band.1.unwrap_or(f64::INFINITY);
+
+        let mut rows = self
+            .cache
+            .iter()
+            .filter(|(mult, _)| (min ..= max).contains(mult))
+            .map(|(mult, list)| (*mult, &**list))
+            .collect::<Vec<_>>();
+
+        if let Some(max_rows) = self.max_rows.filter(|v| *v < rows.len()) {
+            // Multipliers are compared by factor, so ×2 and ×0.5 are equally extreme and immunities are the most
+            // extreme.
+            let distance = |mult: f64| mult.log2().abs();
+            let mut ranked = rows.iter().map(|(mult, _)| *mult).collect::<Vec<_>>();
+
+            // Equally extreme multipliers, such as ×2 and ×½, keep weaknesses ahead of resistances.
+            ranked.sort_by(|a, b| distance(*b).total_cmp(&distance(*a)).then_with(|| b.total_cmp(a)));
+            ranked.truncate(max_rows);
+
+            rows.retain(|(mult, _)| ranked.contains(mult));
+        }
+
+        rows.into_iter()
+    }
+
+    /// Renders the match-up as tab-separated text, followed by the explanation of each multiplier if enabled.
+    pub fn to_text(&mut self) -> String {
+        let mut output = String::new();
+
+        if !self.sources.is_empty() {
+            let sources = self
+                .sources
+                .iter()
+                .map(|(name, stab)| if *stab { format!("{name} (STAB)") } else { name.to_string() })
+                .collect::<Vec<_>>();
+
+            // Writing into a `String` is infallible.
+            let _ = writeln!(output, "Attacking:\t{}\n", sources.join(", "));
+        }
+
+        let (pretty, compact, glyphs) = (self.pretty, self.compact, self.glyphs);
+        let mut groups = Vec::new();
+
+        for (multiplier, type_list) in self.get() {
+            let multiplier = if pretty {
+                format::format_multiplier(multiplier, glyphs)
+            } else {
+                format!("{}{multiplier}", glyphs.times)
+            };
+
+            if compact {
+                groups.push(format!("{multiplier}: {}", type_list.join(", ")));
+            } else {
+                let _ = writeln!(output, "{multiplier}\t{}", type_list.join(", "));
+            }
+        }
+
+        if compact {
+            let _ = writeln!(output, "{}", groups.join(" | "));
+        }
+
+        if self.explain {
+            output.push_str(&self.explanations());
+        }
+
+        // Type names are localized, so they may contain accented letters even when only ASCII may be used.
+        self.glyphs.fold(&output).into_owned()
+    }
+
+    fn explanations(&mut self) -> String {
+        let (is_offensive, glyphs) = (!self.sources.is_empty(), self.glyphs);
+        let buckets = self.get().map(|(multiplier, list)| (multiplier, list.to_vec())).collect::<Vec<_>>();
+        let mut lines = Vec::new();
+
+        for (multiplier, type_list) in buckets {
+            for type_name in &type_list {
+                let Some(factors) = self.explanations.get(type_name) else { continue };
+
+                let factors = factors
+                    .iter()
+                    .map(|(factor, source)| {
+                        let label = match (is_offensive, *factor) {
+                            (true, _) => "via",
+                            // Immunities are already formatted as "immune", which the label would repeat.
+                            (false, 0.0) => "via",
+                            (false, factor) if factor > 1.0 => "weak via",
+                            (false, _) => "resist via",
+                        };
+
+                        match source {
+                            Some(source) => {
+                                format!("{} ({label} {source})", format::format_multiplier(*factor, glyphs))
+                            }
+                            None => format::format_multiplier(*factor, glyphs),
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                lines.push(format!(
+                    "{type_name}:\t{} = {}",
+                    factors.join(" "),
+                    format::format_multiplier(multiplier, glyphs)
+                ));
+            }
+        }
+
+        if lines.is_empty() { String::new() } else { format!("\n{}\n", lines.join("\n")) }
+    }
+
+    /// Renders the match-up as a JSON array of multipliers.
+    pub fn to_json(&mut self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(&self.multipliers())? + "\n")
+    }
+
+    /// Renders the match-up as one JSON object per type.
+    pub fn to_jsonl(&mut self) -> String {
+        self.to_jsonl_with(&Map::new())
+    }
+
+    /// Renders the match-up as one JSON object per type, each also containing the given fields.
+    pub fn to_jsonl_with(&mut self, fields: &Map<String, Value>) -> String {
+        let mut output = String::new();
+
+        for (multiplier, type_list) in self.get() {
+            for type_name in type_list {
+                let mut line = fields.clone();
+
+                line.insert("type".to_owned(), Value::from(&**type_name));
+                line.insert("multiplier".to_owned(), Value::from(multiplier));
+
+                let _ = writeln!(output, "{}", Value::Object(line));
+            }
+        }
+
+        output
+    }
+
+    /// Renders the match-up as a YAML list of multipliers.
+    pub fn to_yaml(&mut self) -> Result<String> {
+        Ok(format::to_yaml(&serde_json::to_value(self.multipliers())?))
+    }
+
+    /// Renders the match-up as comma-separated rows beneath a header row.
+    pub fn to_csv(&mut self) -> String {
+        let mut output = String::from("type,multiplier\n");
+
+        for (multiplier, type_list) in self.get() {
+            for type_name in type_list {
+                let _ = writeln!(output, "{},{multiplier}", format::csv_field(type_name));
+            }
+        }
+
+        output
+    }
+
+    /// Renders the match-up as tab-separated rows beneath a header row.
+    pub fn to_tsv(&mut self) -> String {
+        let mut output = String::from("type\tmultiplier\n");
+
+        for (multiplier, type_list) in self.get() {
+            for type_name in type_list {
+                let _ = writeln!(output, "{}\t{multiplier}", format::tsv_field(type_name));
+            }
+        }
+
+        output
+    }
+
+    /// Renders the match-up as an HTML table, with type names drawn as colored badges.
+    pub fn to_html(&mut self) -> String {
+        let mut output = String::new();
+
+        if !self.sources.is_empty() {
+            let sources = self
+                .sources
+                .iter()
+                .map(
+                    |(name, stab)| {
+                        if *stab { format!("{} (STAB)", format::type_badge(name)) } else { format::type_badge(name) }
+                    },
+                )
+                .collect::<Vec<_>>();
+
+            let _ = writeln!(output, "<p><strong>Attacking:</strong> {}</p>", sources.join(" "));
+        }
+
+        output.push_str("<table>\n<tr><th>Multiplier</th><th>Types</th></tr>\n");
+
+        let (pretty, glyphs) = (self.pretty, self.glyphs);
+
+        for Multiplier { multiplier, types } in self.multipliers() {
+            let multiplier = if pretty {
+                format::format_multiplier(multiplier, glyphs)
+            } else {
+                format!("{}{multiplier}", glyphs.times)
+            };
+            let badges = types.iter().map(|v| format::type_badge(v)).collect::<Vec<_>>();
+
+            let _ =
+                writeln!(output, "<tr><td>{}</td><td>{}</td></tr>", format::html_escape(&multiplier), badges.join(" "));
+        }
+
+        output.push_str("</table>\n");
+
+        output
+    }
+
+    /// Renders the match-up as a Markdown table.
+    pub fn to_markdown(&mut self) -> String {
+        let mut output = String::new();
+
+        if !self.sources.is_empty() {
+            let sources = self
+                .sources
+                .iter()
+                .map(|(name, stab)| if *stab { format!("{name} (STAB)") } else { name.to_string() })
+                .collect::<Vec<_>>();
+
+            let _ = writeln!(output, "**Attacking:** {}\n", sources.join(", "));
+        }
+
+        output.push_str("| Multiplier | Types |\n| --- | --- |\n");
+
+        let (pretty, glyphs) = (self.pretty, self.glyphs);
+
+        for (multiplier, type_list) in self.get() {
+            let multiplier = if pretty {
+                format::format_multiplier(multiplier, glyphs)
+            } else {
+                format!("{}{multiplier}", glyphs.times)
+            };
+
+            let _ = writeln!(output, "| {multiplier} | {} |", format::markdown_cell(&type_list.join(", ")));
+        }
+
+        output
+    }
+}
+
+/// Sorts the given type names and removes any duplicates, which only adjacent duplicates would otherwise allow.
+fn sort_names(names: &mut Vec<Arc<str>>) {
+    names.sort_unstable();
+    names.dedup();
+}
+
+/// Returns whether the given type takes part in match-ups, which excludes types such as "unknown" and "stellar".
+pub fn is_matchup_type(type_: &Type) -> bool {
+    type_.id < 19
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    #[test]
+    fn duplicate_names_are_removed() {
+        let mut names = ["Water", "Fire", "Water", "Grass", "Fire"].map(Arc::from).to_vec();
+
+        super::sort_names(&mut names);
+
+        assert_eq!(names, ["Fire", "Grass", "Water"].map(Arc::from));
+    }
+}
@@ -0,0 +1,39 @@
+use std::io::{IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// The delay between each frame of the spinner's animation.
+const FRAME_DELAY: Duration = Duration::from_millis(80);
+
+/// Whether the spinner is currently displayed.
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Displays an animated spinner using the given frames alongside the given message until [`stop`] is called.
+///
+/// Nothing is displayed if the standard error stream is not a terminal.
+pub fn start(message: String, frames: &'static [char]) {
+    if !std::io::stderr().is_terminal() || ACTIVE.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(FRAME_DELAY);
+
+        for frame in frames.iter().cycle() {
+            interval.tick().await;
+
+            if !ACTIVE.load(Ordering::SeqCst) {
+                break;
+            }
+
+            let _ = write!(std::io::stderr(), "\r{frame} {message}");
+        }
+    });
+}
+
+/// Clears the spinner if it is currently displayed.
+pub fn stop() {
+    if ACTIVE.swap(false, Ordering::SeqCst) {
+        let _ = write!(std::io::stderr(), "\r\x1B[2K");
+    }
+}
@@ -0,0 +1,86 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+use image::imageops::FilterType;
+use image::{GenericImageView, Rgba, RgbaImage};
+use pokesearch::glyphs::Glyphs;
+
+/// The maximum number of columns a rendered sprite may occupy.
+pub const MAX_COLUMNS: u32 = 40;
+
+/// Fetches the sprite at the given URL, reading it from and storing it in the cache where possible.
+pub async fn fetch(cache_dir: &str, url: &str) -> Result<RgbaImage> {
+    let key = format!("sprite:{url}");
+    let bytes = match cacache::read_sync(cache_dir, &key) {
+        Ok(bytes) => bytes,
+        Err(_) => {
+            let bytes = reqwest::get(url).await?.error_for_status()?.bytes().await?.to_vec();
+
+            cacache::write_sync(cache_dir, &key, &bytes)?;
+
+            bytes
+        }
+    };
+
+    Ok(image::load_from_memory(&bytes)?.into_rgba8())
+}
+
+fn crop(image: &RgbaImage) -> RgbaImage {
+    let (width, height) = image.dimensions();
+    let (mut min_x, mut min_y, mut max_x, mut max_y) = (width, height, 0, 0);
+
+    for (x, y, pixel) in image.enumerate_pixels() {
+        if pixel[3] != 0 {
+            min_x = min_x.min(x);
+            min_y = min_y.min(y);
+            max_x = max_x.max(x);
+            max_y = max_y.max(y);
+        }
+    }
+
+    if min_x > max_x || min_y > max_y {
+        return image.clone();
+    }
+
+    image.view(min_x, min_y, max_x - min_x + 1, max_y - min_y + 1).to_image()
+}
+
+pub fn render(image: &RgbaImage, columns: u32, glyphs: Glyphs) -> String {
+    let mut image = self::crop(image);
+
+    if image.width() > columns {
+        let rows = (image.height() * columns).div_ceil(image.width()).max(1);
+
+        image = image::imageops::resize(&image, columns, rows, FilterType::Nearest);
+    }
+
+    let mut output = String::new();
+    let transparent = Rgba([0, 0, 0, 0]);
+
+    for y in (0 .. image.height()).step_by(2) {
+        for x in 0 .. image.width() {
+            let upper = *image.get_pixel(x, y);
+            let lower = if y + 1 < image.height() { *image.get_pixel(x, y + 1) } else { transparent };
+
+            // Writing into a `String` is infallible.
+            let _ = match (upper[3] != 0, lower[3] != 0) {
+                (false, false) => write!(output, "\x1B[0m "),
+                (true, false) => {
+                    write!(output, "\x1B[0m\x1B[38;2;{};{};{}m{}", upper[0], upper[1], upper[2], glyphs.upper_half)
+                }
+                (false, true) => {
+                    write!(output, "\x1B[0m\x1B[38;2;{};{};{}m{}", lower[0], lower[1], lower[2], glyphs.lower_half)
+                }
+                (true, true) => write!(
+                    output,
+                    "\x1B[38;2;{};{};{};48;2;{};{};{}m{}",
+                    upper[0], upper[1], upper[2], lower[0], lower[1], lower[2], glyphs.full
+                ),
+            };
+        }
+
+        output.push_str("\x1B[0m\n");
+    }
+
+    output
+}
@@ -1,188 +1,937 @@
-use std::collections::HashMap;
-use std::sync::Arc;
-
-use anyhow::{Result, bail};
-use rustemon::Follow;
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::io::IsTerminal;
+use std::num::NonZeroUsize;
+use std::ops::RangeInclusive;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+
+use anyhow::Result;
+use http_cache_reqwest::{CACacheManager, Cache, CacheMode, HttpCache, HttpCacheOptions};
+use pokesearch::{LanguagePrefs, lookup};
+use reqwest_middleware::ClientWithMiddleware;
 use rustemon::client::RustemonClient;
-use rustemon::model::pokemon::{Type, TypeRelations};
-use rustemon::model::resource::{Name, NamedApiResource};
+use rustemon::model::evolution::{ChainLink, EvolutionDetail};
+use rustemon::model::games::{Generation, VersionGroup};
+use rustemon::model::moves::Move;
+use rustemon::model::pokemon::PokemonMove;
+use rustemon::model::resource::{ApiResource, Name, NamedApiResource, VerboseEffect};
 use rustemon::model::utility::Language;
+use serde::de::DeserializeOwned;
+use tokio::sync::Semaphore;
+
+use crate::arguments::{ColorChoice, SortBy};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LearnMethod {
+    LevelUp,
+    Egg,
+    Tutor,
+    Machine,
+    Other,
+}
+
+impl LearnMethod {
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "level-up" => Self::LevelUp,
+            "egg" => Self::Egg,
+            "tutor" => Self::Tutor,
+            "machine" => Self::Machine,
+            _ => Self::Other,
+        }
+    }
 
-#[derive(Clone, Debug)]
-pub struct TypeMatchup<'cl> {
-    inner: HashMap<i64, (Arc<str>, f64)>,
-    cache: Vec<(f64, Vec<Arc<str>>)>,
-    client: &'cl RustemonClient,
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::LevelUp => "Level Up",
+            Self::Egg => "Egg",
+            Self::Tutor => "Tutor",
+            Self::Machine => "Machine",
+            Self::Other => "Other",
+        }
+    }
 }
 
-#[allow(unused)]
-impl<'cl> TypeMatchup<'cl> {
-    pub async fn new(client: &'cl RustemonClient) -> Result<Self> {
-        let mut this = Self { inner: HashMap::new(), cache: Vec::new(), client };
+#[macro_export]
+macro_rules! async_print {
+    ($($args:tt)+) => {{
+        $crate::spinner::stop();
 
-        for type_ in rustemon::pokemon::type_::get_all_entries(client).await? {
-            let type_ = type_.follow(client).await?;
+        <_ as ::tokio::io::AsyncWriteExt>::write_all(&mut ::tokio::io::stdout(), ::std::format!($($args)+).as_bytes())
+    }};
+}
 
-            if type_.id < 19 {
-                let type_name = english_search(&type_.names)?.name.to_owned();
+#[macro_export]
+macro_rules! async_println {
+    ($($args:tt)+) => {
+        async {
+            $crate::spinner::stop();
 
-                this.inner.insert(type_.id, (type_name.into(), 1.0));
-            }
+            let mut stdout = ::tokio::io::stdout();
+
+            <_ as ::tokio::io::AsyncWriteExt>::write_all(&mut stdout, ::std::format!($($args)+).as_bytes()).await?;
+            <_ as ::tokio::io::AsyncWriteExt>::write_u8(&mut stdout, b'\n').await
         }
+    };
+}
 
-        Ok(this)
-    }
+#[macro_export]
+macro_rules! async_eprintln {
+    ($($args:tt)+) => {
+        async {
+            $crate::spinner::stop();
 
-    fn modify_type(&mut self, type_: &Type, modify: impl FnOnce(&mut f64)) {
-        if !self.cache.is_empty() {
-            self.cache.clear();
+            let mut stderr = ::tokio::io::stderr();
+
+            <_ as ::tokio::io::AsyncWriteExt>::write_all(&mut stderr, ::std::format!($($args)+).as_bytes()).await?;
+            <_ as ::tokio::io::AsyncWriteExt>::write_u8(&mut stderr, b'\n').await
         }
+    };
+}
+
+/// The languages that localized searches prefer.
+static LANGUAGE_PREFS: OnceLock<LanguagePrefs> = OnceLock::new();
+
+/// Sets the languages that localized searches prefer.
+///
+/// This only has an effect if called before any searches are made.
+pub fn set_language_prefs(prefs: LanguagePrefs) {
+    let _ = LANGUAGE_PREFS.set(prefs);
+}
+
+pub fn language_prefs() -> &'static LanguagePrefs {
+    LANGUAGE_PREFS.get_or_init(LanguagePrefs::default)
+}
+
+/// Whether the API URL of each resolved resource is printed.
+static SHOW_URL: AtomicBool = AtomicBool::new(false);
+
+/// Sets whether the API URL of each resolved resource is printed.
+pub fn set_show_url(show_url: bool) {
+    SHOW_URL.store(show_url, AtomicOrdering::Relaxed);
+}
+
+/// The cache directory that requests are checked against when they are logged, if verbose logging is enabled.
+static VERBOSE_CACHE_DIR: OnceLock<Box<str>> = OnceLock::new();
+
+/// Enables logging each request alongside whether it is served from the given cache directory.
+///
+/// This only has an effect if called before any requests are made.
+pub fn set_verbose(cache_dir: &str) {
+    let _ = VERBOSE_CACHE_DIR.set(cache_dir.into());
+}
+
+/// Logs a request for the given URL and whether it is cached, if verbose logging is enabled.
+pub async fn log_request(url: &str) -> Result<()> {
+    let Some(cache_dir) = VERBOSE_CACHE_DIR.get() else { return Ok(()) };
+    // Responses are cached by the HTTP cache under their method and URL.
+    let is_cached = cacache::metadata_sync(&**cache_dir, format!("GET:{url}")).is_ok_and(|v| v.is_some());
+
+    crate::async_eprintln!("{} '{url}'", if is_cached { "cache hit" } else { "cache miss" }).await.map_err(Into::into)
+}
+
+/// The client used for requests made outside of the API client, sharing its response cache.
+static HTTP_CLIENT: OnceLock<ClientWithMiddleware> = OnceLock::new();
+
+/// Sets the cache directory shared by requests made outside of the API client.
+///
+/// This only has an effect if called before any such requests are made.
+pub fn set_cache_dir(cache_dir: &str) {
+    let _ = HTTP_CLIENT.set(self::http_client(CACacheManager { path: cache_dir.into() }));
+}
+
+fn http_client(manager: CACacheManager) -> ClientWithMiddleware {
+    // The cache is configured as the API client configures its own, so that both read the same responses.
+    let cache = HttpCache { mode: CacheMode::Default, manager, options: HttpCacheOptions::default() };
 
-        self.inner.entry(type_.id).and_modify(|(_, v)| modify(v));
+    reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).with(Cache(cache)).build()
+}
+
+/// Requests the given URL through the shared response cache, returning the status code of its response.
+pub async fn request_status(url: &str) -> Result<u16> {
+    let _permit = self::request_permits().acquire().await?;
+    let client = HTTP_CLIENT.get_or_init(|| self::http_client(CACacheManager::default()));
+
+    self::log_request(url).await?;
+
+    Ok(client.get(url).send().await?.status().as_u16())
+}
+
+/// When output is styled using terminal colors.
+static COLOR: OnceLock<ColorChoice> = OnceLock::new();
+
+/// Sets when output is styled using terminal colors.
+///
+/// This only has an effect if called before any output is written.
+pub fn set_color(choice: ColorChoice) {
+    let _ = COLOR.set(choice);
+}
+
+/// Returns whether output written to the given stream should be styled using terminal colors.
+///
+/// An explicit `--color` choice wins, followed by `NO_COLOR`, then `CLICOLOR_FORCE`, then whether the stream is a
+/// terminal.
+pub fn use_color(stream: &impl IsTerminal) -> bool {
+    let is_set = |name: &str| std::env::var_os(name).is_some_and(|v| !v.is_empty() && v != "0");
+
+    match COLOR.get().copied().unwrap_or_default() {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto if std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) => false,
+        ColorChoice::Auto => is_set("CLICOLOR_FORCE") || stream.is_terminal(),
     }
+}
 
-    pub async fn apply_relations(&mut self, relations: &TypeRelations) -> Result<()> {
-        for type_ in &relations.no_damage_from {
-            self.no_damage_from_resource(type_).await?;
-        }
-        for type_ in &relations.double_damage_from {
-            self.double_damage_from_resource(type_).await?;
-        }
-        for type_ in &relations.half_damage_from {
-            self.half_damage_from_resource(type_).await?;
-        }
+/// A resource that is served by its own API endpoint.
+pub trait Resource {
+    /// The path of the endpoint serving this resource.
+    const ENDPOINT: &'static str;
+
+    fn id(&self) -> i64;
 
-        Ok(())
+    fn url(&self) -> String {
+        format!("https://pokeapi.co/api/v2/{}/{}/", Self::ENDPOINT, self.id())
     }
+}
 
-    pub fn no_damage_from(&mut self, type_: &Type) {
-        self.modify_type(type_, |v| *v = 0.0);
+macro_rules! impl_resource {
+    ($($type:ty => $endpoint:literal),* $(,)?) => {$(
+        impl Resource for $type {
+            const ENDPOINT: &'static str = $endpoint;
+
+            fn id(&self) -> i64 {
+                self.id
+            }
+        }
+    )*};
+}
+
+impl_resource! {
+    rustemon::model::contests::ContestType => "contest-type",
+    rustemon::model::games::Generation => "generation",
+    rustemon::model::games::Pokedex => "pokedex",
+    rustemon::model::games::Version => "version",
+    rustemon::model::items::Item => "item",
+    rustemon::model::items::ItemCategory => "item-category",
+    rustemon::model::machines::Machine => "machine",
+    rustemon::model::moves::Move => "move",
+    rustemon::model::moves::MoveAilment => "move-ailment",
+    rustemon::model::moves::MoveDamageClass => "move-damage-class",
+    rustemon::model::pokemon::Ability => "ability",
+    rustemon::model::pokemon::Characteristic => "characteristic",
+    rustemon::model::pokemon::GrowthRate => "growth-rate",
+    rustemon::model::pokemon::PokeathlonStat => "pokeathlon-stat",
+    rustemon::model::pokemon::Pokemon => "pokemon",
+    rustemon::model::pokemon::PokemonSpecies => "pokemon-species",
+    rustemon::model::pokemon::Stat => "stat",
+    rustemon::model::pokemon::Type => "type",
+}
+
+/// Prints the API URL of the given resource to the standard error stream, if enabled.
+pub async fn print_url<T: Resource>(resource: &T) -> Result<()> {
+    if !SHOW_URL.load(AtomicOrdering::Relaxed) {
+        return Ok(());
     }
 
-    pub fn half_damage_from(&mut self, type_: &Type) {
-        self.modify_type(type_, |v| *v /= 2.0);
+    if self::use_color(&std::io::stderr()) {
+        crate::async_eprintln!("\x1B[2m{}\x1B[0m", resource.url()).await.map_err(Into::into)
+    } else {
+        crate::async_eprintln!("{}", resource.url()).await.map_err(Into::into)
     }
+}
 
-    pub fn double_damage_from(&mut self, type_: &Type) {
-        self.modify_type(type_, |v| *v *= 2.0);
+#[inline]
+pub fn localized_search(list: &[Name]) -> Result<&Name> {
+    self::language_prefs().search(list, |v| &v.language.name)
+}
+
+#[inline]
+pub fn localized_search_by<T>(list: &[T], get_name: impl Fn(&T) -> &NamedApiResource<Language>) -> Result<&T> {
+    self::language_prefs().search(list, |v| &get_name(v).name)
+}
+
+/// Returns every entry in the most preferred language available, in their original order.
+pub fn localized_entries<T>(list: &[T], get_name: impl Fn(&T) -> &NamedApiResource<Language>) -> Vec<&T> {
+    let Ok(entry) = self::localized_search_by(list, &get_name) else { return Vec::new() };
+    let language = &get_name(entry).name;
+
+    list.iter().filter(|v| &get_name(v).name == language).collect()
+}
+
+/// Returns the candidate with the smallest edit distance from the given text, if it is close enough to be a likely
+/// typo.
+pub fn closest_match<'c>(text: &str, candidates: &'c [String]) -> Option<&'c str> {
+    let threshold = (text.chars().count() / 3).max(1);
+
+    candidates
+        .iter()
+        .map(|v| (self::edit_distance(text, v), v))
+        .filter(|(distance, _)| *distance <= threshold)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, v)| &**v)
+}
+
+/// Returns the Levenshtein distance between the given strings.
+fn edit_distance(lhs: &str, rhs: &str) -> usize {
+    let rhs = rhs.chars().collect::<Vec<_>>();
+    let mut previous = (0 ..= rhs.len()).collect::<Vec<_>>();
+    let mut current = vec![0; rhs.len() + 1];
+
+    for (i, lhs_char) in lhs.chars().enumerate() {
+        current[0] = i + 1;
+
+        for (j, rhs_char) in rhs.iter().enumerate() {
+            let substitution = previous[j] + usize::from(lhs_char != *rhs_char);
+
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+
+        std::mem::swap(&mut previous, &mut current);
     }
 
-    pub async fn no_damage_from_name(&mut self, type_: &str) -> Result<()> {
-        let type_ = rustemon::pokemon::type_::get_by_name(type_, self.client).await?;
+    previous[rhs.len()]
+}
+
+#[inline]
+pub fn effect_text(effect: &VerboseEffect, short: bool) -> &str {
+    if short { &effect.short_effect } else { &effect.effect }
+}
+
+/// Greedily wraps each paragraph of the given text to fit within the given column count.
+pub fn wrap_text(text: &str, width: Option<NonZeroUsize>) -> Cow<'_, str> {
+    let Some(width) = width.map(NonZeroUsize::get) else { return Cow::Borrowed(text) };
+    let mut output = String::with_capacity(text.len());
+
+    for (index, line) in text.lines().enumerate() {
+        if index > 0 {
+            output.push('\n');
+        }
 
-        self.modify_type(&type_, |v| *v = 0.0);
+        let mut column = 0;
 
-        Ok(())
+        for word in line.split_whitespace() {
+            let length = word.chars().count();
+
+            if column > 0 && column + 1 + length > width {
+                output.push('\n');
+                column = 0;
+            } else if column > 0 {
+                output.push(' ');
+                column += 1;
+            }
+
+            output.push_str(word);
+            column += length;
+        }
     }
 
-    pub async fn half_damage_from_name(&mut self, type_: &str) -> Result<()> {
-        let type_ = rustemon::pokemon::type_::get_by_name(type_, self.client).await?;
+    Cow::Owned(output)
+}
+
+/// Replaces the effect chance placeholder within the given text, using "-" if the chance is unknown.
+pub fn substitute_effect_chance(text: &str, chance: Option<i64>) -> String {
+    text.replace("$effect_chance", &chance.map_or_else(|| "-".to_owned(), |v| v.to_string()))
+}
 
-        self.modify_type(&type_, |v| *v /= 2.0);
+/// Parses an inclusive range of positive numbers written as `start-end`, or a single number.
+pub fn parse_range(text: &str) -> Option<RangeInclusive<i64>> {
+    let (start, end) = text.split_once('-').unwrap_or((text, text));
+    let (start, end) = (start.trim().parse().ok()?, end.trim().parse().ok()?);
 
-        Ok(())
+    (0 < start && start <= end).then_some(start ..= end)
+}
+
+/// Formats a move's accuracy, noting when a damaging move skips the accuracy check entirely.
+pub fn format_accuracy(accuracy: Option<i64>, damage_class: &str) -> String {
+    match accuracy {
+        Some(accuracy) => accuracy.to_string(),
+        None if damage_class == "status" => "-".to_owned(),
+        None => "- (always hits)".to_owned(),
     }
+}
+
+/// Flattens an evolution chain into its links, depth-first, alongside the depth of each link.
+pub fn flatten_chain(link: &ChainLink) -> Vec<(usize, &ChainLink)> {
+    fn visit<'c>(link: &'c ChainLink, depth: usize, links: &mut Vec<(usize, &'c ChainLink)>) {
+        links.push((depth, link));
+
+        for next in &link.evolves_to {
+            visit(next, depth + 1, links);
+        }
+    }
+
+    let mut links = Vec::new();
 
-    pub async fn double_damage_from_name(&mut self, type_: &str) -> Result<()> {
-        let type_ = rustemon::pokemon::type_::get_by_name(type_, self.client).await?;
+    visit(link, 0, &mut links);
 
-        self.modify_type(&type_, |v| *v *= 2.0);
+    links
+}
 
-        Ok(())
+/// Formats the trigger and every requirement of an evolution detail as a single line.
+pub fn format_evolution_detail(detail: &EvolutionDetail) -> String {
+    let readable = |name: &str| name.replace('-', " ");
+    let mut parts = vec![readable(&detail.trigger.name)];
+
+    let resources = [
+        ("using", detail.item.as_ref().map(|v| &*v.name)),
+        ("holding", detail.held_item.as_ref().map(|v| &*v.name)),
+        ("knowing", detail.known_move.as_ref().map(|v| &*v.name)),
+        ("knowing a move of type", detail.known_move_type.as_ref().map(|v| &*v.name)),
+        ("at", detail.location.as_ref().map(|v| &*v.name)),
+        ("with in party", detail.party_species.as_ref().map(|v| &*v.name)),
+        ("with in party a type", detail.party_type.as_ref().map(|v| &*v.name)),
+        ("traded for", detail.trade_species.as_ref().map(|v| &*v.name)),
+    ];
+
+    parts.extend(resources.into_iter().filter_map(|(label, name)| name.map(|v| format!("{label} {}", readable(v)))));
+
+    parts.extend(detail.min_level.map(|v| format!("level {v}")));
+    parts.extend(detail.min_happiness.map(|v| format!("happiness {v}")));
+    parts.extend(detail.min_beauty.map(|v| format!("beauty {v}")));
+    parts.extend(detail.min_affection.map(|v| format!("affection {v}")));
+    parts.extend(detail.gender.map(|v| if v == 1 { "female".to_owned() } else { "male".to_owned() }));
+    parts.extend(detail.relative_physical_stats.map(|v| match v.cmp(&0) {
+        Ordering::Greater => "attack > defense".to_owned(),
+        Ordering::Less => "attack < defense".to_owned(),
+        Ordering::Equal => "attack = defense".to_owned(),
+    }));
+
+    if !detail.time_of_day.is_empty() {
+        parts.push(format!("during the {}", detail.time_of_day));
+    }
+    if detail.needs_overworld_rain {
+        parts.push("while raining".to_owned());
+    }
+    if detail.turn_upside_down {
+        parts.push("upside down".to_owned());
     }
 
-    pub async fn no_damage_from_resource(&mut self, type_: &NamedApiResource<Type>) -> Result<()> {
-        let type_ = type_.follow(self.client).await?;
+    parts.join(", ")
+}
 
-        self.modify_type(&type_, |v| *v = 0.0);
+/// Returns whether a move with the given target hits several Pokémon at once, reducing its damage in double battles.
+pub fn is_spread_target(target: &str) -> bool {
+    matches!(target, "all-opponents" | "all-other-pokemon")
+}
 
-        Ok(())
+pub fn format_priority(priority: i64) -> String {
+    match priority.cmp(&0) {
+        Ordering::Greater => format!("{priority:+} (moves first)"),
+        Ordering::Less => format!("{priority:+} (moves last)"),
+        Ordering::Equal => priority.to_string(),
     }
+}
 
-    pub async fn half_damage_from_resource(&mut self, type_: &NamedApiResource<Type>) -> Result<()> {
-        let type_ = type_.follow(self.client).await?;
+/// Expands a leading `~` into the home directory, and `$NAME` or `${NAME}` into the value of an environment variable.
+///
+/// Unknown variables are left as-is.
+pub fn expand_path(path: &str) -> String {
+    let mut output = String::with_capacity(path.len());
+    let mut rest = path;
+
+    if let Some(home) = dirs::home_dir().filter(|_| rest == "~" || rest.starts_with("~/")) {
+        output.push_str(&home.to_string_lossy());
+        rest = &rest[1 ..];
+    }
 
-        self.modify_type(&type_, |v| *v /= 2.0);
+    while let Some(index) = rest.find('$') {
+        output.push_str(&rest[.. index]);
+        rest = &rest[index + 1 ..];
 
-        Ok(())
+        let (name, remaining) = match rest.strip_prefix('{').and_then(|v| v.split_once('}')) {
+            Some((name, remaining)) => (name, remaining),
+            None => {
+                let length = rest.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).unwrap_or(rest.len());
+
+                (&rest[.. length], &rest[length ..])
+            }
+        };
+
+        match std::env::var(name) {
+            Ok(value) if !name.is_empty() => output.push_str(&value),
+            _ => {
+                output.push('$');
+                output.push_str(&rest[.. rest.len() - remaining.len()]);
+            }
+        }
+
+        rest = remaining;
     }
 
-    pub async fn double_damage_from_resource(&mut self, type_: &NamedApiResource<Type>) -> Result<()> {
-        let type_ = type_.follow(self.client).await?;
+    output.push_str(rest);
+    output
+}
 
-        self.modify_type(&type_, |v| *v *= 2.0);
+/// A summary of the entries stored within a cache directory.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// The number of cached entries.
+    pub entries: usize,
+    /// The total size of every cached entry, in bytes.
+    pub size: usize,
+    /// The time at which the oldest entry was cached, in milliseconds since the UNIX epoch.
+    pub oldest: Option<u128>,
+    /// The time at which the newest entry was cached, in milliseconds since the UNIX epoch.
+    pub newest: Option<u128>,
+}
+
+pub fn cache_stats(cache_dir: &str) -> CacheStats {
+    cacache::list_sync(cache_dir).filter_map(Result::ok).fold(CacheStats::default(), |stats, v| CacheStats {
+        entries: stats.entries + 1,
+        size: stats.size + v.size,
+        oldest: Some(stats.oldest.map_or(v.time, |t| t.min(v.time))),
+        newest: Some(stats.newest.map_or(v.time, |t| t.max(v.time))),
+    })
+}
+
+pub fn format_size(bytes: usize) -> String {
+    const UNITS: &[&str] = &["KiB", "MiB", "GiB"];
 
-        Ok(())
+    if bytes < 1024 {
+        return format!("{bytes} B");
     }
 
-    pub fn get(&mut self) -> impl Iterator<Item = (f64, &[Arc<str>])> {
-        if self.cache.is_empty() {
-            self.cache = self
-                .inner
-                .iter()
-                .fold(HashMap::<u16, Vec<Arc<str>>>::new(), |mut map, (_, (name, mult))| {
-                    map.entry((*mult * 100.0).round() as u16).or_default().push(Arc::clone(name));
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
 
-                    map
-                })
-                .into_iter()
-                .map(|(m, mut v)| {
-                    v.dedup();
-                    v.sort_unstable();
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
 
-                    (m as f64 / 100.0, v)
-                })
-                .collect::<Vec<_>>();
+    format!("{size:.1} {}", UNITS[unit])
+}
 
-            self.cache.sort_unstable_by_key(|(m, _)| (*m * 100.0) as u16);
-            self.cache.reverse();
-        }
+/// Formats the given number of milliseconds since the UNIX epoch as a UTC date and time.
+pub fn format_timestamp(millis: u128) -> String {
+    let seconds = (millis / 1000) as i64;
+    let (days, seconds) = (seconds.div_euclid(86_400), seconds.rem_euclid(86_400));
+
+    // Converts days since the epoch into a civil date, as described by Howard Hinnant's `civil_from_days`.
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!("{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02} UTC", seconds / 3600, seconds % 3600 / 60, seconds % 60)
+}
+
+/// The default maximum number of requests that may be issued at the same time.
+pub const DEFAULT_CONCURRENCY: usize = 8;
+
+/// Limits the number of requests issued at the same time by concurrent helpers.
+static REQUEST_PERMITS: OnceLock<Semaphore> = OnceLock::new();
+
+/// Sets the maximum number of requests that may be issued at the same time.
+///
+/// This has no effect once any concurrent requests have been issued.
+pub fn set_concurrency(limit: usize) {
+    let _ = REQUEST_PERMITS.set(Semaphore::new(limit));
+}
+
+fn request_permits() -> &'static Semaphore {
+    REQUEST_PERMITS.get_or_init(|| Semaphore::new(DEFAULT_CONCURRENCY))
+}
+
+/// Follows resources like [`rustemon::Follow`], logging each request if verbose logging is enabled.
+pub trait Follow<T> {
+    async fn follow(&self, client: &RustemonClient) -> Result<T, rustemon::error::Error>;
+}
 
-        self.cache.iter().map(|(mult, list)| (*mult, &**list))
+impl<T: DeserializeOwned + Send + Sync> Follow<T> for NamedApiResource<T> {
+    async fn follow(&self, client: &RustemonClient) -> Result<T, rustemon::error::Error> {
+        let _ = self::log_request(&self.url).await;
+
+        rustemon::Follow::follow(self, client).await
     }
+}
 
-    pub async fn print(&mut self) -> Result<()> {
-        for (multiplier, type_list) in self.get() {
-            crate::async_println!("×{multiplier}\t{}", type_list.join(", ")).await?;
-        }
+impl<T: DeserializeOwned + Send + Sync> Follow<T> for ApiResource<T> {
+    async fn follow(&self, client: &RustemonClient) -> Result<T, rustemon::error::Error> {
+        let _ = self::log_request(&self.url).await;
 
-        Ok(())
+        rustemon::Follow::follow(self, client).await
     }
 }
 
-#[macro_export]
-macro_rules! async_print {
-    ($($args:tt)+) => {
-        <_ as ::tokio::io::AsyncWriteExt>::write_all(&mut ::tokio::io::stdout(), ::std::format!($($args)+).as_bytes())
-    };
+/// Follows each of the given resources, returning them in the same order.
+pub async fn follow_all<T>(client: &RustemonClient, resources: &[NamedApiResource<T>]) -> Result<Vec<T>>
+where
+    T: DeserializeOwned + Send + Sync,
+{
+    futures::future::try_join_all(resources.iter().map(|v| async move {
+        let _permit = self::request_permits().acquire().await?;
+
+        v.follow(client).await.map_err(Into::into)
+    }))
+    .await
 }
 
-#[macro_export]
-macro_rules! async_println {
-    ($($args:tt)+) => {
-        async {
-            let mut stdout = ::tokio::io::stdout();
+/// Returns the entries on the given one-indexed page, alongside the total number of pages.
+pub fn paginate<T>(entries: &[T], page: usize, page_size: usize) -> Option<(&[T], usize)> {
+    let page_count = entries.len().div_ceil(page_size).max(1);
 
-            <_ as ::tokio::io::AsyncWriteExt>::write_all(&mut stdout, ::std::format!($($args)+).as_bytes()).await?;
-            <_ as ::tokio::io::AsyncWriteExt>::write_u8(&mut stdout, b'\n').await
-        }
-    };
+    if page == 0 || page > page_count {
+        return None;
+    }
+
+    let start = (page - 1) * page_size;
+
+    Some((&entries[start .. (start + page_size).min(entries.len())], page_count))
 }
 
 #[inline]
-pub fn linear_search<T>(list: &[T], predicate: impl Fn(&&T) -> bool) -> Result<&T> {
-    match list.iter().find(predicate).or_else(|| list.first()) {
-        Some(value) => Ok(value),
-        None => bail!("unable to find a suitable value"),
+pub fn resource_entries<T>(resources: Vec<NamedApiResource<T>>) -> Vec<(Option<i64>, String)> {
+    resources.into_iter().map(|v| (lookup::resource_id(&v.url), v.name)).collect()
+}
+
+/// Sorts the given resources by their names or by the identifiers within their URLs.
+pub fn sort_resources<T>(resources: &mut [NamedApiResource<T>], sort_by: SortBy) {
+    match sort_by {
+        SortBy::Id => resources.sort_by_key(|v| lookup::resource_id(&v.url)),
+        SortBy::Name => resources.sort_by(|a, b| a.name.cmp(&b.name)),
     }
 }
 
+pub fn latest_version_group(
+    moves: &[PokemonMove],
+    predicate: impl Fn(&NamedApiResource<VersionGroup>) -> bool,
+) -> Option<&NamedApiResource<VersionGroup>> {
+    moves
+        .iter()
+        .flat_map(|v| &v.version_group_details)
+        .map(|v| &v.version_group)
+        .filter(|v| predicate(v))
+        .max_by_key(|v| lookup::resource_id(&v.url))
+}
+
 #[inline]
-pub fn english_search(list: &[Name]) -> Result<&Name> {
-    self::linear_search(list, |v| v.language.name == "en")
+pub fn is_in_generation(generation: &Generation, version_group: &NamedApiResource<VersionGroup>) -> bool {
+    generation.version_groups.iter().any(|v| v.name == version_group.name)
 }
 
 #[inline]
-pub fn english_search_by<T>(list: &[T], get_name: impl Fn(&T) -> &NamedApiResource<Language>) -> Result<&T> {
-    self::linear_search(list, |v| get_name(v).name == "en")
+pub fn normalize_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+pub fn group_learnset<'p>(
+    moves: &'p [PokemonMove],
+    version_group: &str,
+) -> BTreeMap<LearnMethod, Vec<(i64, &'p NamedApiResource<Move>)>> {
+    let mut learnset = BTreeMap::<LearnMethod, Vec<_>>::new();
+
+    for move_ in moves {
+        for details in move_.version_group_details.iter().filter(|v| v.version_group.name == version_group) {
+            let method = LearnMethod::from_name(&details.move_learn_method.name);
+
+            learnset.entry(method).or_default().push((details.level_learned_at, &move_.move_));
+        }
+    }
+
+    for list in learnset.values_mut() {
+        list.sort_unstable_by(|(a_level, a), (b_level, b)| a_level.cmp(b_level).then_with(|| a.name.cmp(&b.name)));
+        list.dedup_by(|(a_level, a), (b_level, b)| a_level == b_level && a.name == b.name);
+    }
+
+    learnset
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::sync::Arc;
+
+    use pokesearch::{Direction, Glyphs, LanguagePrefs, TypeMatchup};
+    use rustemon::client::{RustemonClient, RustemonClientBuilder};
+    use rustemon::model::pokemon::Type;
+    use rustemon::model::resource::{Name, NamedApiResource};
+
+    /// A type's name, alongside the types it deals double, half, and no damage to.
+    type Relations = (&'static str, &'static [&'static str], &'static [&'static str], &'static [&'static str]);
+
+    /// Every type's relations, ordered by identifier.
+    const TYPE_CHART: [Relations; 18] = [
+        ("normal", &[], &["rock", "steel"], &["ghost"]),
+        ("fighting", &["normal", "rock", "steel", "ice", "dark"], &["flying", "poison", "bug", "psychic", "fairy"], &[
+            "ghost",
+        ]),
+        ("flying", &["fighting", "bug", "grass"], &["rock", "steel", "electric"], &[]),
+        ("poison", &["grass", "fairy"], &["poison", "ground", "rock", "ghost"], &["steel"]),
+        ("ground", &["poison", "rock", "steel", "fire", "electric"], &["bug", "grass"], &["flying"]),
+        ("rock", &["flying", "bug", "fire", "ice"], &["fighting", "ground", "steel"], &[]),
+        (
+            "bug",
+            &["grass", "psychic", "dark"],
+            &["fighting", "flying", "poison", "ghost", "steel", "fire", "fairy"],
+            &[],
+        ),
+        ("ghost", &["ghost", "psychic"], &["dark"], &["normal"]),
+        ("steel", &["rock", "ice", "fairy"], &["steel", "fire", "water", "electric"], &[]),
+        ("fire", &["bug", "steel", "grass", "ice"], &["rock", "fire", "water", "dragon"], &[]),
+        ("water", &["ground", "rock", "fire"], &["water", "grass", "dragon"], &[]),
+        ("grass", &["ground", "rock", "water"], &["flying", "poison", "bug", "steel", "fire", "grass", "dragon"], &[]),
+        ("electric", &["flying", "water"], &["grass", "electric", "dragon"], &["ground"]),
+        ("psychic", &["fighting", "poison"], &["steel", "psychic"], &["dark"]),
+        ("ice", &["flying", "ground", "grass", "dragon"], &["steel", "fire", "water", "ice"], &[]),
+        ("dragon", &["dragon"], &["steel"], &["fairy"]),
+        ("dark", &["ghost", "psychic"], &["fighting", "dark", "fairy"], &[]),
+        ("fairy", &["fighting", "dragon", "dark"], &["poison", "steel", "fire"], &[]),
+    ];
+
+    fn resource<T: Default>(name: &str, url: String) -> NamedApiResource<T> {
+        let mut resource = NamedApiResource::default();
+
+        resource.name = name.to_owned();
+        resource.url = url;
+
+        resource
+    }
+
+    fn type_id(name: &str) -> i64 {
+        TYPE_CHART.iter().position(|(v, ..)| *v == name).expect("the type should be within the chart") as i64 + 1
+    }
+
+    pub fn type_resources(names: &[&str]) -> Vec<NamedApiResource<Type>> {
+        names
+            .iter()
+            .map(|v| self::resource(v, format!("https://pokeapi.co/api/v2/type/{}/", self::type_id(v))))
+            .collect()
+    }
+
+    /// Returns the English name of the given type, as displayed within match-ups.
+    pub fn type_name(name: &str) -> String {
+        name[.. 1].to_uppercase() + &name[1 ..]
+    }
+
+    /// Builds the given type alongside its damage relations, without requesting it.
+    pub fn type_fixture(name: &str) -> Type {
+        let id = self::type_id(name);
+        let (_, double_to, half_to, no_to) = TYPE_CHART[id as usize - 1];
+        // Relations received are found by searching every type's dealt relations for the given type.
+        let from = |relation: usize| {
+            let attackers = TYPE_CHART.iter().filter(|v| [v.1, v.2, v.3][relation].contains(&name));
+
+            self::type_resources(&attackers.map(|v| v.0).collect::<Vec<_>>())
+        };
+
+        let mut type_ = Type { id, name: name.to_owned(), ..Type::default() };
+
+        type_.names = vec![Name {
+            name: self::type_name(name),
+            language: self::resource("en", "https://pokeapi.co/api/v2/language/9/".to_owned()),
+        }];
+        type_.damage_relations.double_damage_to = self::type_resources(double_to);
+        type_.damage_relations.half_damage_to = self::type_resources(half_to);
+        type_.damage_relations.no_damage_to = self::type_resources(no_to);
+        type_.damage_relations.double_damage_from = from(0);
+        type_.damage_relations.half_damage_from = from(1);
+        type_.damage_relations.no_damage_from = from(2);
+
+        type_
+    }
+
+    /// Returns a client that is never used to make requests.
+    pub fn client() -> RustemonClient {
+        RustemonClientBuilder::default().try_build().expect("the default client should be valid")
+    }
+
+    /// Returns a match-up over every type within the chart, without requesting any.
+    pub fn matchup(client: &RustemonClient) -> TypeMatchup<'_> {
+        let types = TYPE_CHART.iter().map(|(v, ..)| self::type_fixture(v)).collect::<Vec<_>>();
+
+        TypeMatchup::from_types(client, &types, LanguagePrefs::default())
+            .expect("every fixture should have an English name")
+    }
+
+    /// Returns the multiplier applied to the given type within the match-up.
+    pub fn multiplier(matchup: &mut TypeMatchup, name: &str) -> f64 {
+        let name = self::type_name(name);
+
+        matchup.get().find(|(_, types)| types.iter().any(|v| **v == *name)).map(|(v, _)| v).expect("missing type")
+    }
+
+    fn defensive_matchup<'cl>(client: &'cl RustemonClient, types: &[&str]) -> TypeMatchup<'cl> {
+        let mut matchup = self::matchup(client);
+
+        for type_ in types {
+            matchup.apply_type(&self::type_fixture(type_), Direction::Defensive).expect("the fixture should apply");
+        }
+
+        matchup
+    }
+
+    #[test]
+    fn immunity_wins_over_weakness() {
+        let client = self::client();
+        let mut matchup = self::defensive_matchup(&client, &["ground", "flying"]);
+
+        assert_eq!(self::multiplier(&mut matchup, "electric"), 0.0);
+        assert_eq!(self::multiplier(&mut matchup, "ground"), 0.0);
+        assert_eq!(self::multiplier(&mut matchup, "ice"), 4.0);
+    }
+
+    #[test]
+    fn resistances_combine_into_quarters() {
+        let client = self::client();
+        let mut matchup = self::defensive_matchup(&client, &["grass", "dragon"]);
+
+        assert_eq!(self::multiplier(&mut matchup, "water"), 0.25);
+        assert_eq!(self::multiplier(&mut matchup, "electric"), 0.25);
+    }
+
+    #[test]
+    fn resistance_cancels_weakness() {
+        let client = self::client();
+        let mut matchup = self::defensive_matchup(&client, &["grass", "ground"]);
+
+        assert_eq!(self::multiplier(&mut matchup, "water"), 1.0);
+        assert_eq!(self::multiplier(&mut matchup, "electric"), 0.0);
+        assert_eq!(self::multiplier(&mut matchup, "ice"), 4.0);
+    }
+
+    #[test]
+    fn immunity_ignores_application_order() {
+        let client = self::client();
+        let mut forward = self::defensive_matchup(&client, &["ground", "flying"]);
+        let mut reverse = self::defensive_matchup(&client, &["flying", "ground"]);
+
+        assert_eq!(self::multiplier(&mut forward, "electric"), 0.0);
+        assert_eq!(self::multiplier(&mut reverse, "electric"), 0.0);
+        assert_eq!(forward.multipliers(), reverse.multipliers());
+
+        let electric = &self::type_resources(&["electric"])[0];
+        let mut doubled_first = self::matchup(&client);
+        let mut immune_first = self::matchup(&client);
+
+        doubled_first.double_damage_from_resource(electric).unwrap();
+        doubled_first.double_damage_from_resource(electric).unwrap();
+        doubled_first.no_damage_from_resource(electric).unwrap();
+        immune_first.no_damage_from_resource(electric).unwrap();
+        immune_first.double_damage_from_resource(electric).unwrap();
+        immune_first.double_damage_from_resource(electric).unwrap();
+
+        assert_eq!(self::multiplier(&mut doubled_first, "electric"), 0.0);
+        assert_eq!(self::multiplier(&mut immune_first, "electric"), 0.0);
+    }
+
+    #[test]
+    fn offensive_fire_matchup() {
+        let client = self::client();
+        let fire = self::type_fixture("fire");
+        let mut relations = self::matchup(&client);
+        let mut coverage = self::matchup(&client);
+
+        relations.apply_type(&fire, Direction::Offensive).unwrap();
+        coverage.apply_offensive_relations(&fire, true).unwrap();
+
+        for matchup in [&mut relations, &mut coverage] {
+            assert_eq!(self::multiplier(matchup, "grass"), 2.0);
+            assert_eq!(self::multiplier(matchup, "water"), 0.5);
+            assert_eq!(self::multiplier(matchup, "normal"), 1.0);
+        }
+    }
+
+    #[test]
+    fn three_types_reach_eighths() {
+        let client = self::client();
+        let mut matchup = self::defensive_matchup(&client, &["grass", "bug", "steel"]);
+
+        assert_eq!(self::multiplier(&mut matchup, "fire"), 8.0);
+        assert_eq!(self::multiplier(&mut matchup, "grass"), 0.125);
+    }
+
+    #[test]
+    fn types_sharing_a_name_are_listed_once() {
+        // A second type sharing an English name lands in the same bucket, but is only listed once.
+        let client = self::client();
+        let mut types = ["fire", "water", "grass"].map(self::type_fixture).to_vec();
+        let mut duplicate = self::type_fixture("fire");
+
+        duplicate.id = 19;
+        types.push(duplicate);
+
+        let mut matchup = TypeMatchup::from_types(&client, &types, LanguagePrefs::default()).unwrap();
+        let names = matchup.get().flat_map(|(_, types)| types.iter().cloned()).collect::<Vec<_>>();
+
+        assert_eq!(names, ["Fire", "Grass", "Water"].map(Arc::from));
+    }
+
+    #[test]
+    fn equally_extreme_rows_rank_weaknesses_first() {
+        let client = self::client();
+        let mut matchup = self::defensive_matchup(&client, &["fire"]);
+
+        matchup.set_max_rows(Some(1));
+
+        assert_eq!(matchup.get().map(|(v, _)| v).collect::<Vec<_>>(), [2.0]);
+
+        matchup.set_max_rows(Some(2));
+
+        assert_eq!(matchup.get().map(|(v, _)| v).collect::<Vec<_>>(), [2.0, 0.5]);
+    }
+
+    #[test]
+    fn effect_chance_is_substituted() {
+        let text = "Has a $effect_chance% chance to burn the target.";
+
+        assert_eq!(super::substitute_effect_chance(text, Some(10)), "Has a 10% chance to burn the target.");
+        assert_eq!(super::substitute_effect_chance(text, None), "Has a -% chance to burn the target.");
+        assert_eq!(super::substitute_effect_chance("Inflicts damage.", None), "Inflicts damage.");
+    }
+
+    #[test]
+    fn localized_entries_keep_one_language() {
+        let name = |name: &str, language: &str| Name {
+            name: name.to_owned(),
+            language: self::resource(language, format!("https://pokeapi.co/api/v2/language/{language}/")),
+        };
+        let names = [name("Salamèche", "fr"), name("Charmander", "en"), name("Hitokage", "ja"), name("Ember", "en")];
+        let entries = super::localized_entries(&names, |v| &v.language);
+
+        assert_eq!(entries.iter().map(|v| &*v.name).collect::<Vec<_>>(), ["Charmander", "Ember"]);
+    }
+
+    #[test]
+    fn jsonl_rows_contain_the_given_fields() {
+        let client = self::client();
+        let mut matchup = self::defensive_matchup(&client, &["ground", "flying"]);
+        let fields = serde_json::Map::from_iter([("id".to_owned(), serde_json::Value::from(472))]);
+
+        matchup.set_band(Some(4.0), None);
+
+        assert_eq!(matchup.to_jsonl_with(&fields), "{\"id\":472,\"multiplier\":4.0,\"type\":\"Ice\"}\n");
+    }
+
+    #[test]
+    fn ascii_only_matchups_render_as_ascii() {
+        let client = self::client();
+        let mut matchup = self::defensive_matchup(&client, &["grass", "ground", "flying"]);
+
+        matchup.set_pretty(true);
+        matchup.set_explain(true);
+        matchup.set_glyphs(Glyphs::ASCII);
+
+        let text = matchup.to_text();
+
+        assert!(text.is_ascii(), "{text}");
+        assert!(text.contains("x1/2\tFighting, Grass"), "{text}");
+        assert!(text.contains("immune"));
+    }
+
+    #[test]
+    fn explained_immunities_name_their_source_once() {
+        let client = self::client();
+        let mut matchup = self::defensive_matchup(&client, &["grass", "ground"]);
+
+        matchup.set_explain(true);
+
+        assert!(matchup.to_text().contains("Electric:\t×½ (resist via Grass) immune (via Ground) = immune"));
+    }
 }